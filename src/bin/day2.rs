@@ -1,4 +1,8 @@
+#[cfg(feature = "parallel")]
+use advent_of_code_2025::parallel::compare_timings;
 use radixal::IntoDigits;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::error::Error;
 use std::num::ParseIntError;
 use std::ops::RangeInclusive;
@@ -18,51 +22,80 @@ fn parse_input(input: &str) -> Result<Vec<RangeInclusive<u64>>, ParseIntError> {
         .collect()
 }
 
+fn is_repeated_twice(x: u64) -> bool {
+    let divider = 10_u64.pow((x.nbr_decimal_digits() / 2) as u32);
+    x / divider == x % divider
+}
+
+fn is_repeated(x: u64) -> bool {
+    let l = x.nbr_decimal_digits();
+    // Retain only lengths that evenly divide the number.
+    (1..=l / 2)
+        .filter(|size| l.is_multiple_of(*size))
+        .any(|size| {
+            // Use the divider to break the number in equal-sized pieces, then compare
+            // each new piece with the first one.
+            let divider = 10_u64.pow(size as u32);
+            let initial = x % divider;
+            let mut remainder = x / divider;
+            while remainder > 0 {
+                if remainder % divider != initial {
+                    return false;
+                }
+                remainder /= divider;
+            }
+            true
+        })
+}
+
 fn part1(ranges: &[RangeInclusive<u64>]) -> u64 {
     ranges
         .iter()
-        .flat_map(|range| {
-            range.clone().filter(|x| {
-                let divider = 10_u64.pow((x.nbr_decimal_digits() / 2) as u32);
-                x / divider == x % divider
-            })
-        })
+        .flat_map(|range| range.clone().filter(|&x| is_repeated_twice(x)))
         .sum()
 }
 
 fn part2(ranges: &[RangeInclusive<u64>]) -> u64 {
     ranges
         .iter()
+        .flat_map(|range| range.clone().filter(|&x| is_repeated(x)))
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+fn part1_parallel(ranges: &[RangeInclusive<u64>]) -> u64 {
+    ranges
+        .par_iter()
         .flat_map(|range| {
-            range.clone().filter(|x| {
-                let l = x.nbr_decimal_digits();
-                // Retain only lengths that evenly divide the number.
-                (1..=l / 2).filter(|size| l % size == 0).any(|size| {
-                    // Use the divider to break the number in equal-sized pieces, then compare
-                    // each new piece with the first one.
-                    let divider = 10_u64.pow(size as u32);
-                    let initial = x % divider;
-                    let mut remainder = x / divider;
-                    while remainder > 0 {
-                        if remainder % divider != initial {
-                            return false;
-                        }
-                        remainder /= divider;
-                    }
-                    true
-                })
-            })
+            range
+                .clone()
+                .into_par_iter()
+                .filter(|&x| is_repeated_twice(x))
         })
         .sum()
 }
 
+#[cfg(feature = "parallel")]
+fn part2_parallel(ranges: &[RangeInclusive<u64>]) -> u64 {
+    ranges
+        .par_iter()
+        .flat_map(|range| range.clone().into_par_iter().filter(|&x| is_repeated(x)))
+        .sum()
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let input = std::fs::read_to_string(INPUT)?;
     let ranges = parse_input(&input)?;
 
+    #[cfg(feature = "parallel")]
+    let part1 = compare_timings("First", || part1(&ranges), || part1_parallel(&ranges));
+    #[cfg(not(feature = "parallel"))]
     let part1 = part1(&ranges);
     println!("First answer: {}", part1);
 
+    #[cfg(feature = "parallel")]
+    let part2 = compare_timings("Second", || part2(&ranges), || part2_parallel(&ranges));
+    #[cfg(not(feature = "parallel"))]
     let part2 = part2(&ranges);
     println!("Second answer: {}", part2);
 
@@ -94,4 +127,22 @@ mod tests {
         const EXPECTED: u64 = 4174379265;
         assert_eq!(EXPECTED, result);
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_part1_parallel() {
+        let ranges = parse_input(INPUT).unwrap();
+        let result = part1_parallel(&ranges);
+        const EXPECTED: u64 = 1227775554;
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_part2_parallel() {
+        let ranges = parse_input(INPUT).unwrap();
+        let result = part2_parallel(&ranges);
+        const EXPECTED: u64 = 4174379265;
+        assert_eq!(EXPECTED, result);
+    }
 }
@@ -1,3 +1,7 @@
+#[cfg(feature = "parallel")]
+use advent_of_code_2025::parallel::compare_timings;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::error::Error;
 use std::num::ParseIntError;
 use std::str::FromStr;
@@ -46,6 +50,16 @@ fn part2(banks: &[Bank]) -> u64 {
     banks.iter().map(|b| b.maximum_joltage(12)).sum()
 }
 
+#[cfg(feature = "parallel")]
+fn part1_parallel(banks: &[Bank]) -> u64 {
+    banks.par_iter().map(|b| b.maximum_joltage(2)).sum()
+}
+
+#[cfg(feature = "parallel")]
+fn part2_parallel(banks: &[Bank]) -> u64 {
+    banks.par_iter().map(|b| b.maximum_joltage(12)).sum()
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let input = std::fs::read_to_string(INPUT)?;
     let banks: Vec<Bank> = input
@@ -53,9 +67,15 @@ fn main() -> Result<(), Box<dyn Error>> {
         .map(|line| line.parse())
         .collect::<Result<_, _>>()?;
 
+    #[cfg(feature = "parallel")]
+    let part1 = compare_timings("First", || part1(&banks), || part1_parallel(&banks));
+    #[cfg(not(feature = "parallel"))]
     let part1 = part1(&banks);
     println!("First answer: {}", part1);
 
+    #[cfg(feature = "parallel")]
+    let part2 = compare_timings("Second", || part2(&banks), || part2_parallel(&banks));
+    #[cfg(not(feature = "parallel"))]
     let part2 = part2(&banks);
     println!("Second answer: {}", part2);
 
@@ -95,4 +115,30 @@ mod tests {
         const EXPECTED: u64 = 3121910778619;
         assert_eq!(EXPECTED, result);
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_part1_parallel() {
+        let banks: Vec<Bank> = INPUT
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let result = part1_parallel(&banks);
+        const EXPECTED: u64 = 357;
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_part2_parallel() {
+        let banks: Vec<Bank> = INPUT
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let result = part2_parallel(&banks);
+        const EXPECTED: u64 = 3121910778619;
+        assert_eq!(EXPECTED, result);
+    }
 }
@@ -1,8 +1,18 @@
 use advent_of_code_2025::grid::{Grid, Position};
+use advent_of_code_2025::image::{FrameWriter, ImageFormat, Rgb};
+use std::collections::HashSet;
 use std::error::Error;
+use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 const INPUT: &str = "./inputs/day4.txt";
+const REMOVED: char = 'x';
+const FRAME_DELAY: Duration = Duration::from_millis(150);
+const IMAGE_SCALE: usize = 4;
+const EMPTY_COLOUR: Rgb = [0, 0, 0];
+const PAPER_COLOUR: Rgb = [0, 160, 0];
+const REMOVED_COLOUR: Rgb = [220, 0, 0];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Tile {
@@ -22,6 +32,15 @@ impl TryFrom<char> for Tile {
     }
 }
 
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
+        match value {
+            Tile::Empty => '.',
+            Tile::Paper => '@',
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct PrintingDepartment(Grid<Tile>);
 
@@ -42,6 +61,51 @@ impl PrintingDepartment {
             }
         })
     }
+
+    // Yields each generation along with the rolls removed from it, until none can be removed.
+    fn generations(&self) -> impl Iterator<Item = (PrintingDepartment, Vec<Position>)> {
+        self.frames().take_while(|(_, removed)| !removed.is_empty())
+    }
+
+    // Like `generations`, followed by the final, stable state with nothing left to remove.
+    fn frames(&self) -> impl Iterator<Item = (PrintingDepartment, Vec<Position>)> {
+        let mut department = Some(self.clone());
+        std::iter::from_fn(move || {
+            let generation = department.take()?;
+            let removable: Vec<Position> = generation.removable_paper_rolls().collect();
+            if !removable.is_empty() {
+                let mut next = generation.clone();
+                for &position in &removable {
+                    next.0[position] = Tile::Empty;
+                }
+                department = Some(next);
+            }
+
+            Some((generation, removable))
+        })
+    }
+
+    fn render(&self, removed: &[Position], colour: bool) -> String {
+        let removed: HashSet<Position> = removed.iter().copied().collect();
+        let mut output = String::with_capacity(self.0.height() * (self.0.width() + 1));
+
+        for (position, &tile) in self.0.iter() {
+            if position.x() == 0 && position.y() != 0 {
+                output.push('\n');
+            }
+
+            match (removed.contains(&position), colour) {
+                (true, true) => output.push_str(&format!("\x1b[1;31m{}\x1b[0m", REMOVED)),
+                (true, false) => output.push(REMOVED),
+                (false, true) if tile == Tile::Paper => {
+                    output.push_str(&format!("\x1b[32m{}\x1b[0m", char::from(tile)))
+                }
+                (false, _) => output.push(char::from(tile)),
+            }
+        }
+
+        output
+    }
 }
 
 impl FromStr for PrintingDepartment {
@@ -65,23 +129,82 @@ fn part1(department: &PrintingDepartment) -> usize {
 }
 
 fn part2(department: &PrintingDepartment) -> usize {
-    let mut department = department.clone();
-    std::iter::from_fn(move || {
-        let removable: Vec<Position> = department.removable_paper_rolls().collect();
-        if removable.is_empty() {
-            return None;
+    department
+        .generations()
+        .map(|(_, removed)| removed.len())
+        .sum()
+}
+
+fn removal_table(counts: &[usize]) -> String {
+    let mut table = String::from("Generation | Removed | Total\n-----------+---------+------\n");
+    let mut total = 0;
+    for (generation, count) in counts.iter().enumerate() {
+        total += count;
+        table.push_str(&format!(
+            "{:>10} | {:>7} | {:>5}\n",
+            generation + 1,
+            count,
+            total
+        ));
+    }
+    table
+}
+
+fn animate(department: &PrintingDepartment) -> Vec<usize> {
+    let mut counts = Vec::new();
+    for (generation, removed) in department.frames() {
+        // Clear the screen and move the cursor back to the top left corner.
+        print!("\x1b[2J\x1b[H");
+        println!("{}", generation.render(&removed, true));
+        if removed.is_empty() {
+            println!("Generation {}: nothing left to remove", counts.len() + 1);
+        } else {
+            println!("Generation {}: {} removed", counts.len() + 1, removed.len());
+            counts.push(removed.len());
+            std::thread::sleep(FRAME_DELAY);
         }
+    }
+    counts
+}
 
-        let count = removable.len();
-        for position in removable {
-            if let Some(tile) = department.0.get_mut(position) {
-                *tile = Tile::Empty;
-            }
+fn write_frames(department: &PrintingDepartment, directory: &Path) -> std::io::Result<Vec<usize>> {
+    std::fs::create_dir_all(directory)?;
+
+    let mut counts = Vec::new();
+    for (generation, removed) in department.frames() {
+        let frame = directory.join(format!("generation_{:03}.txt", counts.len() + 1));
+        std::fs::write(frame, generation.render(&removed, false))?;
+        if !removed.is_empty() {
+            counts.push(removed.len());
         }
+    }
+    Ok(counts)
+}
 
-        Some(count)
-    })
-    .sum()
+fn write_images(department: &PrintingDepartment, directory: &Path) -> std::io::Result<Vec<usize>> {
+    #[cfg(feature = "png")]
+    let format = ImageFormat::Png;
+    #[cfg(not(feature = "png"))]
+    let format = ImageFormat::Ppm;
+    let mut frames = FrameWriter::new(directory, format, IMAGE_SCALE)?;
+
+    let mut counts = Vec::new();
+    for (generation, removed) in department.frames() {
+        let removed_set: HashSet<Position> = removed.iter().copied().collect();
+        frames.write_grid(&generation.0, |position, &tile| {
+            if removed_set.contains(&position) {
+                REMOVED_COLOUR
+            } else if tile == Tile::Paper {
+                PAPER_COLOUR
+            } else {
+                EMPTY_COLOUR
+            }
+        })?;
+        if !removed.is_empty() {
+            counts.push(removed.len());
+        }
+    }
+    Ok(counts)
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -94,6 +217,22 @@ fn main() -> Result<(), Box<dyn Error>> {
     let part2 = part2(&department);
     println!("Second answer: {}", part2);
 
+    let mut args = std::env::args().skip(1);
+    let counts = match args.next().as_deref() {
+        Some("--animate") => animate(&department),
+        Some("--frames") => {
+            let directory = args.next().ok_or("Usage: day4 --frames <directory>")?;
+            write_frames(&department, Path::new(&directory))?
+        }
+        Some("--images") => {
+            let directory = args.next().ok_or("Usage: day4 --images <directory>")?;
+            write_images(&department, Path::new(&directory))?
+        }
+        Some(arg) => return Err(format!("Unknown argument: {}", arg).into()),
+        None => return Ok(()),
+    };
+    print!("{}", removal_table(&counts));
+
     Ok(())
 }
 
@@ -129,4 +268,82 @@ mod tests {
         const EXPECTED: usize = 43;
         assert_eq!(EXPECTED, result);
     }
+
+    #[test]
+    fn test_generations() {
+        let department: PrintingDepartment = INPUT.parse().unwrap();
+        let result: Vec<usize> = department
+            .generations()
+            .map(|(_, removed)| removed.len())
+            .collect();
+        const EXPECTED: [usize; 9] = [13, 12, 7, 5, 2, 1, 1, 1, 1];
+        assert_eq!(EXPECTED.as_slice(), result);
+    }
+
+    #[test]
+    fn test_frames() {
+        let department: PrintingDepartment = INPUT.parse().unwrap();
+        let frames: Vec<(PrintingDepartment, Vec<Position>)> = department.frames().collect();
+        let (last, removed) = frames.last().unwrap();
+
+        assert_eq!(department.generations().count() + 1, frames.len());
+        assert!(removed.is_empty());
+
+        let (previous, removed_last) = department.generations().last().unwrap();
+        let patch = previous.0.diff(&last.0).unwrap();
+        assert_eq!(removed_last, patch.positions().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Snapshot(#[serde(with = "advent_of_code_2025::grid::serde_text")] Grid<Tile>);
+
+        let department: PrintingDepartment = INPUT.parse().unwrap();
+        let (after_first_round, _) = department.generations().nth(1).unwrap();
+        let json = serde_json::to_string(&Snapshot(after_first_round.0.clone())).unwrap();
+        let restored = PrintingDepartment(serde_json::from_str::<Snapshot>(&json).unwrap().0);
+
+        assert_eq!(after_first_round, restored);
+        const EXPECTED: usize = 30;
+        assert_eq!(EXPECTED, part2(&restored));
+    }
+
+    #[test]
+    fn test_diff_between_generations() {
+        let department: PrintingDepartment = INPUT.parse().unwrap();
+        let mut generations = department.generations();
+        let (first, removed) = generations.next().unwrap();
+        let (second, _) = generations.next().unwrap();
+        let patch = first.0.diff(&second.0).unwrap();
+
+        assert_eq!(removed, patch.positions().collect::<Vec<_>>());
+        assert!(
+            patch
+                .changes()
+                .iter()
+                .all(|change| (change.old, change.new) == (Tile::Paper, Tile::Empty))
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let department: PrintingDepartment = INPUT.parse().unwrap();
+        let (generation, removed) = department.generations().next().unwrap();
+        let result = generation.render(&removed, false);
+        const EXPECTED: &str = "\
+            ..xx.xx@x.\n\
+            x@@.@.@.@@\n\
+            @@@@@.x.@@\n\
+            @.@@@@..@.\n\
+            x@.@@@@.@x\n\
+            .@@@@@@@.@\n\
+            .@.@.@.@@@\n\
+            x.@@@.@@@@\n\
+            .@@@@@@@@.\n\
+            x.x.@@@.x.\
+        ";
+        assert_eq!(EXPECTED, result);
+    }
 }
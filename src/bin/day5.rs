@@ -1,3 +1,7 @@
+#[cfg(feature = "parallel")]
+use advent_of_code_2025::parallel::compare_timings;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::error::Error;
 use std::ops::RangeInclusive;
 
@@ -38,6 +42,21 @@ fn part1(fresh_ingredients: &[FreshIngredients], available_ingredients: &[Ingred
         .count()
 }
 
+#[cfg(feature = "parallel")]
+fn part1_parallel(
+    fresh_ingredients: &[FreshIngredients],
+    available_ingredients: &[Ingredient],
+) -> usize {
+    available_ingredients
+        .par_iter()
+        .filter(|ingredient| {
+            fresh_ingredients
+                .iter()
+                .any(|fresh| fresh.contains(ingredient))
+        })
+        .count()
+}
+
 fn part2(fresh_ingredients: &[FreshIngredients]) -> u64 {
     let mut ranges: Vec<FreshIngredients> = Vec::new();
 
@@ -73,6 +92,15 @@ fn main() -> Result<(), Box<dyn Error>> {
     let input = std::fs::read_to_string(INPUT)?;
     let (fresh_ingredients, available_ingredients) = parse_input(&input);
 
+    // Merging the ranges in the second part is inherently sequential, so only the first
+    // part has a parallel variant.
+    #[cfg(feature = "parallel")]
+    let part1 = compare_timings(
+        "First",
+        || part1(&fresh_ingredients, &available_ingredients),
+        || part1_parallel(&fresh_ingredients, &available_ingredients),
+    );
+    #[cfg(not(feature = "parallel"))]
     let part1 = part1(&fresh_ingredients, &available_ingredients);
     println!("First answer: {}", part1);
 
@@ -109,6 +137,16 @@ mod tests {
         assert_eq!(EXPECTED, result);
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_part1_parallel() {
+        let (available_ingredients, fresh_ingredients) = parse_input(INPUT);
+        let result = part1_parallel(&available_ingredients, &fresh_ingredients);
+
+        const EXPECTED: usize = 3;
+        assert_eq!(EXPECTED, result);
+    }
+
     #[test]
     fn test_part2() {
         let (available_ingredients, _) = parse_input(INPUT);
@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+const BIN_DIRECTORY: &str = "./src/bin";
+const INPUT_DIRECTORY: &str = "./inputs";
+const DAY_PLACEHOLDER: &str = "{DAY}";
+
+const TEMPLATE: &str = r#"use std::error::Error;
+
+const INPUT: &str = "./inputs/day{DAY}.txt";
+
+fn part1(_input: &str) -> u64 {
+    todo!()
+}
+
+fn part2(_input: &str) -> u64 {
+    todo!()
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = std::fs::read_to_string(INPUT)?;
+
+    let part1 = part1(&input);
+    println!("First answer: {}", part1);
+
+    let part2 = part2(&input);
+    println!("Second answer: {}", part2);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+    ";
+
+    #[test]
+    fn test_part1() {
+        let result = part1(INPUT);
+
+        const EXPECTED: u64 = 0;
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = part2(INPUT);
+
+        const EXPECTED: u64 = 0;
+        assert_eq!(EXPECTED, result);
+    }
+}
+"#;
+
+fn render_template(day: u32) -> String {
+    TEMPLATE.replace(DAY_PLACEHOLDER, &day.to_string())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let day: u32 = std::env::args()
+        .nth(1)
+        .ok_or("Usage: scaffold <day>")?
+        .parse()?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day: {}", day).into());
+    }
+
+    // Every file in `src/bin` is picked up by cargo as its own binary, so writing the
+    // source file is all it takes for `cargo run --bin dayN` to work.
+    let source = Path::new(BIN_DIRECTORY).join(format!("day{}.rs", day));
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&source)
+    {
+        Ok(mut file) => file.write_all(render_template(day).as_bytes())?,
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            return Err(format!("Day {} already exists: {}", day, source.display()).into());
+        }
+        Err(e) => return Err(e.into()),
+    }
+    println!("Created {}", source.display());
+
+    // Inputs are personal to each account, so only create an empty placeholder and leave
+    // any existing one alone.
+    std::fs::create_dir_all(INPUT_DIRECTORY)?;
+    let input = Path::new(INPUT_DIRECTORY).join(format!("day{}.txt", day));
+    match OpenOptions::new().write(true).create_new(true).open(&input) {
+        Ok(_) => println!("Created {}", input.display()),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing {}", input.display())
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let result = render_template(7);
+
+        assert!(result.contains("const INPUT: &str = \"./inputs/day7.txt\";"));
+        assert!(!result.contains(DAY_PLACEHOLDER));
+        // The parts are stubs, so their parameter must not trigger `unused_variables`.
+        assert!(result.contains("fn part1(_input: &str)"));
+    }
+}
@@ -0,0 +1,443 @@
+use crate::grid::{Grid, GridLike, Position};
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Not};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// Cells are stored row by row, each row starting on a fresh word, with the cell at `x` in bit
+// `x % 64` of word `x / 64`. Bits past the width are always kept clear, so that whole words can
+// be compared, counted and hashed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    height: usize,
+    width: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(height: usize, width: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        BitGrid {
+            height,
+            width,
+            words_per_row,
+            words: vec![0; height * words_per_row],
+        }
+    }
+
+    pub fn from_grid<T, F: Fn(&T) -> bool>(grid: &Grid<T>, predicate: F) -> Self {
+        let mut bits = BitGrid::new(grid.height(), grid.width());
+        for (position, value) in grid.iter() {
+            if predicate(value) {
+                bits.set(position, true);
+            }
+        }
+        bits
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::new(
+            self.height,
+            self.width,
+            self.iter().map(|(_, value)| value).collect(),
+        )
+        .unwrap()
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn index(&self, position: Position) -> Option<(usize, u64)> {
+        if position.x() < self.width && position.y() < self.height {
+            let word = position.y() * self.words_per_row + position.x() / WORD_BITS;
+            Some((word, 1 << (position.x() % WORD_BITS)))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, position: Position) -> Option<bool> {
+        self.index(position)
+            .map(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    /// Returns the previous value, or `None` if the position is outside the grid.
+    pub fn set(&mut self, position: Position, value: bool) -> Option<bool> {
+        let (word, mask) = self.index(position)?;
+        let previous = self.words[word] & mask != 0;
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+        Some(previous)
+    }
+
+    fn row_range(&self, idy: usize) -> Option<std::ops::Range<usize>> {
+        (idy < self.height).then(|| idy * self.words_per_row..(idy + 1) * self.words_per_row)
+    }
+
+    pub fn row_words(&self, idy: usize) -> Option<&[u64]> {
+        self.row_range(idy).map(|range| &self.words[range])
+    }
+
+    /// Bits past the width must be left clear.
+    pub fn row_words_mut(&mut self, idy: usize) -> Option<&mut [u64]> {
+        self.row_range(idy).map(|range| &mut self.words[range])
+    }
+
+    pub fn row(&self, idy: usize) -> Option<impl Iterator<Item = bool>> {
+        let words = self.row_words(idy)?;
+        Some((0..self.width).map(move |idx| words[idx / WORD_BITS] & (1 << (idx % WORD_BITS)) != 0))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = bool>> {
+        (0..self.height).map(|idy| self.row(idy).unwrap())
+    }
+
+    /// Combines row `idy` with `words`, laid out as in `row_words`. Returns `None` if the row
+    /// is outside the grid, and panics if `words` does not have one word per word of the row.
+    pub fn row_and(&mut self, idy: usize, words: &[u64]) -> Option<()> {
+        self.zip_row(idy, words, |a, b| *a &= b)
+    }
+
+    pub fn row_or(&mut self, idy: usize, words: &[u64]) -> Option<()> {
+        self.zip_row(idy, words, |a, b| *a |= b)
+    }
+
+    pub fn row_xor(&mut self, idy: usize, words: &[u64]) -> Option<()> {
+        self.zip_row(idy, words, |a, b| *a ^= b)
+    }
+
+    /// Moves every cell of row `idy` `amount` cells towards `x = 0`, clearing the cells left
+    /// behind. Returns `None` if the row is outside the grid.
+    pub fn shift_row_left(&mut self, idy: usize, amount: usize) -> Option<()> {
+        let (word_shift, bit_shift) = (amount / WORD_BITS, amount % WORD_BITS);
+        let row = self.row_words_mut(idy)?;
+        let old = row.to_vec();
+        for (word, value) in row.iter_mut().enumerate() {
+            let low = old.get(word + word_shift).map_or(0, |w| w >> bit_shift);
+            let high = match bit_shift {
+                0 => 0,
+                _ => old
+                    .get(word + word_shift + 1)
+                    .map_or(0, |w| w << (WORD_BITS - bit_shift)),
+            };
+            *value = low | high;
+        }
+        Some(())
+    }
+
+    /// Moves every cell of row `idy` `amount` cells away from `x = 0`, dropping the cells
+    /// pushed past the width. Returns `None` if the row is outside the grid.
+    pub fn shift_row_right(&mut self, idy: usize, amount: usize) -> Option<()> {
+        let (word_shift, bit_shift) = (amount / WORD_BITS, amount % WORD_BITS);
+        let row = self.row_words_mut(idy)?;
+        let old = row.to_vec();
+        for (word, value) in row.iter_mut().enumerate() {
+            let high = word
+                .checked_sub(word_shift)
+                .map_or(0, |source| old[source] << bit_shift);
+            let low = match bit_shift {
+                0 => 0,
+                _ => word
+                    .checked_sub(word_shift + 1)
+                    .map_or(0, |source| old[source] >> (WORD_BITS - bit_shift)),
+            };
+            *value = high | low;
+        }
+        self.clear_row_padding(idy);
+        Some(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, bool)> {
+        self.rows().enumerate().flat_map(|(idy, row)| {
+            row.enumerate()
+                .map(move |(idx, value)| (Position::new(idx, idy), value))
+        })
+    }
+
+    /// Iterates over the positions of the set cells only, skipping empty words entirely.
+    pub fn ones(&self) -> impl Iterator<Item = Position> {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let idy = index / self.words_per_row;
+                let offset = (index % self.words_per_row) * WORD_BITS;
+                // Repeatedly clear the lowest set bit until none remain.
+                std::iter::successors((word != 0).then_some(word), |w| {
+                    Some(w & (w - 1)).filter(|&w| w != 0)
+                })
+                .map(move |w| Position::new(offset + w.trailing_zeros() as usize, idy))
+            })
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn and_not(&mut self, other: &BitGrid) {
+        self.zip_words(other, |a, b| *a &= !b);
+    }
+
+    /// Counts the set cells among the eight neighbours of every cell, a word at a time, and
+    /// returns the grid of cells whose count satisfies `predicate`.
+    pub fn neighbour_counts_matching<F: Fn(u8) -> bool>(&self, predicate: F) -> BitGrid {
+        let mut result = BitGrid::new(self.height, self.width);
+        let empty = vec![0; self.words_per_row];
+
+        for idy in 0..self.height {
+            let above = idy
+                .checked_sub(1)
+                .and_then(|above| self.row_words(above))
+                .unwrap_or(&empty);
+            let current = self.row_words(idy).unwrap();
+            let below = self.row_words(idy + 1).unwrap_or(&empty);
+
+            for word in 0..self.words_per_row {
+                // Bit-sliced counter: `planes[k]` holds bit `k` of each cell's count.
+                let mut planes = [0_u64; 4];
+                let mut add = |input: u64| {
+                    let mut carry = input;
+                    for plane in planes.iter_mut() {
+                        let next = *plane & carry;
+                        *plane ^= carry;
+                        carry = next;
+                    }
+                };
+
+                for (row, include_centre) in [(above, true), (current, false), (below, true)] {
+                    add(shifted_from_left(row, word));
+                    add(shifted_from_right(row, word));
+                    if include_centre {
+                        add(row[word]);
+                    }
+                }
+
+                let matching = (0..=8)
+                    .filter(|&count| predicate(count))
+                    .fold(0, |acc, count| {
+                        acc | planes.iter().enumerate().fold(!0, |mask, (k, plane)| {
+                            if count & (1 << k) != 0 {
+                                mask & plane
+                            } else {
+                                mask & !plane
+                            }
+                        })
+                    });
+                result.words[idy * self.words_per_row + word] = matching;
+            }
+        }
+
+        result.clear_padding();
+        result
+    }
+
+    fn zip_words<F: Fn(&mut u64, u64)>(&mut self, other: &BitGrid, f: F) {
+        assert_eq!(
+            (self.height, self.width),
+            (other.height, other.width),
+            "grid dimensions differ"
+        );
+        for (a, &b) in self.words.iter_mut().zip(&other.words) {
+            f(a, b);
+        }
+    }
+
+    fn zip_row<F: Fn(&mut u64, u64)>(&mut self, idy: usize, words: &[u64], f: F) -> Option<()> {
+        let row = self.row_words_mut(idy)?;
+        assert_eq!(row.len(), words.len(), "row lengths differ");
+        for (a, &b) in row.iter_mut().zip(words) {
+            f(a, b);
+        }
+        self.clear_row_padding(idy);
+        Some(())
+    }
+
+    fn padding_mask(&self) -> Option<u64> {
+        let used = self.width % WORD_BITS;
+        (used != 0).then(|| (1 << used) - 1)
+    }
+
+    fn clear_row_padding(&mut self, idy: usize) {
+        if let Some(mask) = self.padding_mask() {
+            self.words[(idy + 1) * self.words_per_row - 1] &= mask;
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        if let Some(mask) = self.padding_mask() {
+            for row in self.words.chunks_exact_mut(self.words_per_row) {
+                *row.last_mut().unwrap() &= mask;
+            }
+        }
+    }
+}
+
+// Each bit receives the value of the cell to its left (`x - 1`), or 0 at the left edge.
+fn shifted_from_left(row: &[u64], word: usize) -> u64 {
+    let carry = if word > 0 { row[word - 1] >> 63 } else { 0 };
+    (row[word] << 1) | carry
+}
+
+// Each bit receives the value of the cell to its right (`x + 1`), or 0 at the right edge.
+fn shifted_from_right(row: &[u64], word: usize) -> u64 {
+    let carry = row.get(word + 1).map_or(0, |w| w << 63);
+    (row[word] >> 1) | carry
+}
+
+// Only the set cells count as occupied.
+impl GridLike for BitGrid {
+    type Item = bool;
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn get(&self, position: Position) -> Option<&bool> {
+        BitGrid::get(self, position).map(|value| if value { &true } else { &false })
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Position, &bool)> {
+        self.ones().map(|position| (position, &true))
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |a, b| *a &= b);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |a, b| *a |= b);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |a, b| *a ^= b);
+    }
+}
+
+impl Not for BitGrid {
+    type Output = BitGrid;
+
+    fn not(mut self) -> Self::Output {
+        self.words.iter_mut().for_each(|w| *w = !*w);
+        self.clear_padding();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+        ..@@.@@@@.\n\
+        @@@.@.@.@@\n\
+        @@@@@.@.@@\n\
+        @.@@@@..@.\n\
+        @@.@@@@.@@\n\
+        .@@@@@@@.@\n\
+        .@.@.@.@@@\n\
+        @.@@@.@@@@\n\
+        .@@@@@@@@.\n\
+        @.@.@@@.@.\
+    ";
+
+    fn parse(input: &str) -> BitGrid {
+        let height = input.lines().count();
+        let width = input.lines().next().unwrap().len();
+        let grid = Grid::new(height, width, input.lines().flat_map(str::chars).collect());
+        BitGrid::from_grid(&grid.unwrap(), |&c| c == '@')
+    }
+
+    fn removable(paper: &BitGrid) -> BitGrid {
+        let mut removable = paper.neighbour_counts_matching(|count| count < 4);
+        removable &= paper;
+        removable
+    }
+
+    #[test]
+    fn test_neighbour_counts_matching() {
+        let paper = parse(INPUT);
+        let result = removable(&paper).count_ones();
+        const EXPECTED: usize = 13;
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[test]
+    fn test_neighbour_counts_across_words() {
+        // Cells either side of a word boundary must see each other, on every row.
+        let mut grid = BitGrid::new(3, 130);
+        for x in 62..66 {
+            grid.set(Position::new(x, 1), true);
+        }
+        let result: Vec<Position> = grid
+            .neighbour_counts_matching(|count| count == 2)
+            .ones()
+            .collect();
+        let expected: Vec<Position> = [(62, 0), (65, 0), (63, 1), (64, 1), (62, 2), (65, 2)]
+            .into_iter()
+            .map(|(x, y)| Position::new(x, y))
+            .collect();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_row_operations() {
+        let mut grid = BitGrid::new(2, 70);
+        assert!(grid.row(2).is_none());
+        assert_eq!(None, grid.row_or(2, &[0, 0]));
+
+        // Setting every bit, padding included, must leave the padding clear.
+        grid.row_or(0, &[!0, !0]).unwrap();
+        assert_eq!(70, grid.count_ones());
+        grid.row_xor(0, &[0, 1 << 5]).unwrap();
+        assert_eq!(Some(false), grid.get(Position::new(69, 0)));
+
+        grid.shift_row_left(0, 66).unwrap();
+        let result: Vec<bool> = grid.row(0).unwrap().take(5).collect();
+        assert_eq!(vec![true, true, true, false, false], result);
+
+        grid.shift_row_right(0, 64).unwrap();
+        let result: Vec<Position> = grid.ones().collect();
+        let expected: Vec<Position> = (64..67).map(|x| Position::new(x, 0)).collect();
+        assert_eq!(expected, result);
+
+        let first = grid.row_words(0).unwrap().to_vec();
+        grid.row_or(1, &first).unwrap();
+        grid.row_and(0, &[0, 0]).unwrap();
+        assert_eq!(Some(&[0, 0b111][..]), grid.row_words(1));
+        assert_eq!(3, grid.count_ones());
+    }
+
+    #[test]
+    fn test_repeated_removal() {
+        let mut paper = parse(INPUT);
+        let mut result = 0;
+        loop {
+            let removable = removable(&paper);
+            if removable.count_ones() == 0 {
+                break;
+            }
+            result += removable.count_ones();
+            paper.and_not(&removable);
+        }
+        const EXPECTED: usize = 43;
+        assert_eq!(EXPECTED, result);
+    }
+}
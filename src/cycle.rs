@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// The shape of an iterated process that eventually repeats: the states from step `start` on
+/// come back every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as at step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Floyd's tortoise and hare, using constant memory. The state space must be finite, or this
+/// never returns.
+pub fn floyd<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Brent's algorithm, using constant memory and fewer steps than `floyd`. The state space must
+/// be finite, or this never returns.
+pub fn brent<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: Fn(&S) -> S,
+{
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// The states in the order they were reached, until `limit` steps have been taken or a state
+/// comes back. Each state is stored once and looked up by hash, which suits states that are
+/// expensive to step through, such as whole grids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History<S> {
+    pub states: Vec<S>,
+    /// `None` if the limit was reached first.
+    pub cycle: Option<Cycle>,
+}
+
+impl<S: Eq + Hash> History<S> {
+    pub fn new<F: FnMut(&S) -> S>(initial: S, mut step: F, limit: usize) -> Self {
+        let fingerprint = |state: &S| {
+            let mut hasher = DefaultHasher::new();
+            state.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        seen.entry(fingerprint(&initial)).or_default().push(0);
+        let mut states = vec![initial];
+
+        while states.len() <= limit {
+            let next = step(states.last().unwrap());
+            let candidates = seen.entry(fingerprint(&next)).or_default();
+            if let Some(&start) = candidates.iter().find(|&&idx| states[idx] == next) {
+                let period = states.len() - start;
+                return History {
+                    states,
+                    cycle: Some(Cycle { start, period }),
+                };
+            }
+            candidates.push(states.len());
+            states.push(next);
+        }
+
+        History {
+            states,
+            cycle: None,
+        }
+    }
+
+    /// The state after `n` steps, provided the history reaches it or found a cycle.
+    pub fn state_at(&self, n: usize) -> Option<&S> {
+        match self.cycle {
+            Some(cycle) => self.states.get(cycle.equivalent_step(n)),
+            None => self.states.get(n),
+        }
+    }
+
+    /// The sum of `value` over the states reached by the first `n` steps, not counting the
+    /// initial one. Returns `None` under the same conditions as `state_at`.
+    pub fn accumulated_at<V: Fn(&S) -> i64>(&self, value: V, n: usize) -> Option<i64> {
+        let mut sums = vec![0];
+        for state in &self.states[1..] {
+            sums.push(sums.last().unwrap() + value(state));
+        }
+
+        let Some(Cycle { start, period }) = self.cycle else {
+            return sums.get(n).copied();
+        };
+        if n < sums.len() {
+            return Some(sums[n]);
+        }
+
+        // The last step of each period lands back on `start`, past the end of `sums`.
+        let per_period = sums[start + period - 1] - sums[start] + value(&self.states[start]);
+        let repeats = ((n - start) / period) as i64;
+        let remainder = (n - start) % period;
+        Some(sums[start] + repeats * per_period + sums[start + remainder] - sums[start])
+    }
+}
+
+/// The state after `n` steps, jumping over every full period once the process repeats.
+pub fn state_at<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let history = History::new(initial, step, n);
+    history.state_at(n).unwrap().clone()
+}
+
+/// The sum of `value` over the states reached by the first `n` steps, not counting the initial
+/// one, jumping over every full period once the process repeats.
+pub fn accumulated_at<S, F, V>(initial: S, step: F, value: V, n: usize) -> i64
+where
+    S: Eq + Hash,
+    F: FnMut(&S) -> S,
+    V: Fn(&S) -> i64,
+{
+    let history = History::new(initial, step, n);
+    history.accumulated_at(value, n).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Position, Transform};
+
+    // 3, 10, 101, 2, 5, 26, 167, 95, then back to 101.
+    fn next(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_floyd_brent() {
+        const EXPECTED: Cycle = Cycle {
+            start: 2,
+            period: 6,
+        };
+        assert_eq!(EXPECTED, floyd(3, next));
+        assert_eq!(EXPECTED, brent(3, next));
+        assert_eq!(Some(EXPECTED), History::new(3, next, usize::MAX).cycle);
+        assert_eq!(None, History::new(3, next, 5).cycle);
+    }
+
+    #[test]
+    fn test_jump_ahead() {
+        assert_eq!(5, state_at(3, next, 1_000_000_000));
+        assert_eq!(101, state_at(3, next, 2));
+
+        let brute_force = |n: usize| {
+            std::iter::successors(Some(3), |x| Some(next(x)))
+                .skip(1)
+                .take(n)
+                .sum::<u64>() as i64
+        };
+        for n in [0, 1, 7, 8, 9, 1000] {
+            assert_eq!(brute_force(n), accumulated_at(3, next, |&x| x as i64, n));
+        }
+    }
+
+    #[test]
+    fn test_grid_states() {
+        let grid: Grid<u32> = Grid::new(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        let rotate = |grid: &Grid<u32>| grid.transform(Transform::Rotate90);
+        let history = History::new(grid.clone(), rotate, usize::MAX);
+
+        assert_eq!(
+            Some(Cycle {
+                start: 0,
+                period: 4
+            }),
+            history.cycle
+        );
+        assert_eq!(Some(&rotate(&grid)), history.state_at(1_000_000_001));
+
+        let corner = |grid: &Grid<u32>| grid[Position::new(0, 0)] as i64;
+        let per_period: i64 = (1..=4).map(|n| corner(&history.states[n % 4])).sum();
+        assert_eq!(
+            Some(per_period * 250_000_000),
+            history.accumulated_at(corner, 1_000_000_000)
+        );
+    }
+}
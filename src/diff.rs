@@ -0,0 +1,133 @@
+use crate::grid::{Grid, Position};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change<T> {
+    pub position: Position,
+    pub old: T,
+    pub new: T,
+}
+
+/// The cells that differ between two grids of the same dimensions, in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Patch<T> {
+    changes: Vec<Change<T>>,
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    /// Returns the changes turning `self` into `other`, or `None` if their dimensions differ.
+    pub fn diff(&self, other: &Grid<T>) -> Option<Patch<T>> {
+        if (self.height(), self.width()) != (other.height(), other.width()) {
+            return None;
+        }
+
+        let changes = self
+            .iter()
+            .zip(other.iter())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((position, old), (_, new))| Change {
+                position,
+                old: old.clone(),
+                new: new.clone(),
+            })
+            .collect();
+        Some(Patch { changes })
+    }
+}
+
+impl<T: Clone> Patch<T> {
+    pub fn changes(&self) -> &[Change<T>] {
+        &self.changes
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        self.changes.iter().map(|change| change.position)
+    }
+
+    /// Sets every changed cell to its new value. Panics if a position is outside the grid.
+    pub fn apply(&self, grid: &mut Grid<T>) {
+        for change in &self.changes {
+            grid[change.position] = change.new.clone();
+        }
+    }
+
+    /// Sets every changed cell back to its old value. Panics if a position is outside the grid.
+    pub fn revert(&self, grid: &mut Grid<T>) {
+        for change in &self.changes {
+            grid[change.position] = change.old.clone();
+        }
+    }
+
+    pub fn inverse(&self) -> Patch<T> {
+        Patch {
+            changes: self
+                .changes
+                .iter()
+                .map(|change| Change {
+                    position: change.position,
+                    old: change.new.clone(),
+                    new: change.old.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Renders `grid` one character per cell, with the changed cells highlighted in bold red.
+    pub fn render<F: Fn(&T) -> char>(&self, grid: &Grid<T>, f: F) -> String {
+        let mut changed = self.positions().peekable();
+        let mut output = String::new();
+
+        for (position, value) in grid.iter() {
+            if position.x() == 0 && position.y() != 0 {
+                output.push('\n');
+            }
+
+            if changed.next_if_eq(&position).is_some() {
+                output.push_str(&format!("\x1b[1;31m{}\x1b[0m", f(value)));
+            } else {
+                output.push(f(value));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_apply_revert() {
+        let old: Grid<char> = ["#.#", "..."].iter().map(|row| row.chars()).collect();
+        let new: Grid<char> = ["#..", ".#."].iter().map(|row| row.chars()).collect();
+        let patch = old.diff(&new).unwrap();
+
+        let expected = vec![Position::new(2, 0), Position::new(1, 1)];
+        assert_eq!(expected, patch.positions().collect::<Vec<_>>());
+
+        let mut grid = old.clone();
+        patch.apply(&mut grid);
+        assert_eq!(new, grid);
+        patch.revert(&mut grid);
+        assert_eq!(old, grid);
+        assert_eq!(old.diff(&new), new.diff(&old).map(|patch| patch.inverse()));
+    }
+
+    #[test]
+    fn test_render() {
+        let old: Grid<char> = ["#.", ".."].iter().map(|row| row.chars()).collect();
+        let new: Grid<char> = ["..", ".#"].iter().map(|row| row.chars()).collect();
+        let result = old.diff(&new).unwrap().render(&new, |&c| c);
+
+        const EXPECTED: &str = "\x1b[1;31m.\x1b[0m.\n.\x1b[1;31m#\x1b[0m";
+        assert_eq!(EXPECTED, result);
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlowEdge {
+    to: usize,
+    capacity: u64,
+    residual: u64,
+}
+
+/// A capacity graph over the nodes `0..len`. Every edge is stored next to its reverse, so that
+/// edge `id` and edge `id ^ 1` always form a pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowNetwork {
+    edges: Vec<FlowEdge>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+/// A partition of the nodes in two, along with the edges crossing from one side to the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    pub capacity: u64,
+    pub source_side: Vec<usize>,
+    pub sink_side: Vec<usize>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl FlowNetwork {
+    pub fn new(len: usize) -> Self {
+        FlowNetwork {
+            edges: Vec::new(),
+            outgoing: vec![Vec::new(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outgoing.is_empty()
+    }
+
+    /// Adds an edge that only carries flow from `from` to `to`, returning its id.
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: u64) -> usize {
+        self.add_edge_pair(from, to, capacity, 0)
+    }
+
+    /// Adds an edge that carries up to `capacity` in either direction, returning its id.
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, capacity: u64) -> usize {
+        self.add_edge_pair(a, b, capacity, capacity)
+    }
+
+    fn add_edge_pair(&mut self, from: usize, to: usize, forward: u64, backward: u64) -> usize {
+        let id = self.edges.len();
+        for (a, b, capacity) in [(from, to, forward), (to, from, backward)] {
+            self.outgoing[a].push(self.edges.len());
+            self.edges.push(FlowEdge {
+                to: b,
+                capacity,
+                residual: capacity,
+            });
+        }
+        id
+    }
+
+    /// The flow currently going through the edge in its own direction.
+    pub fn flow(&self, edge: usize) -> u64 {
+        self.edges[edge]
+            .capacity
+            .saturating_sub(self.edges[edge].residual)
+    }
+
+    /// Dinic's algorithm. Flow pushed by earlier calls is kept, so only the extra flow is
+    /// returned. Panics if `source` and `sink` are the same node.
+    pub fn max_flow(&mut self, source: usize, sink: usize) -> u64 {
+        assert_ne!(source, sink, "source and sink must be different nodes");
+        let mut total = 0;
+        while let Some(levels) = self.levels(source, sink) {
+            let mut next = vec![0; self.len()];
+            loop {
+                let pushed = self.augment(source, sink, u64::MAX, &levels, &mut next);
+                if pushed == 0 {
+                    break;
+                }
+                total += pushed;
+            }
+        }
+        total
+    }
+
+    // Breadth-first distances from `source` through the residual graph, or `None` if `sink`
+    // cannot be reached anymore.
+    fn levels(&self, source: usize, sink: usize) -> Option<Vec<usize>> {
+        let mut levels = vec![usize::MAX; self.len()];
+        let mut queue = VecDeque::from([source]);
+        levels[source] = 0;
+
+        while let Some(node) = queue.pop_front() {
+            for &edge in &self.outgoing[node] {
+                let FlowEdge { to, residual, .. } = self.edges[edge];
+                if residual > 0 && levels[to] == usize::MAX {
+                    levels[to] = levels[node] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        (levels[sink] != usize::MAX).then_some(levels)
+    }
+
+    fn augment(
+        &mut self,
+        node: usize,
+        sink: usize,
+        limit: u64,
+        levels: &[usize],
+        next: &mut [usize],
+    ) -> u64 {
+        if node == sink {
+            return limit;
+        }
+
+        while let Some(&edge) = self.outgoing[node].get(next[node]) {
+            let FlowEdge { to, residual, .. } = self.edges[edge];
+            if residual > 0 && levels[to] == levels[node] + 1 {
+                let pushed = self.augment(to, sink, limit.min(residual), levels, next);
+                if pushed > 0 {
+                    self.edges[edge].residual -= pushed;
+                    self.edges[edge ^ 1].residual += pushed;
+                    return pushed;
+                }
+            }
+            next[node] += 1;
+        }
+
+        0
+    }
+
+    /// Saturates the network from `source` to `sink` and returns the resulting minimum cut,
+    /// whose source side holds every node still reachable through the residual graph. Panics if
+    /// `source` and `sink` are the same node.
+    pub fn min_cut(&mut self, source: usize, sink: usize) -> MinCut {
+        self.max_flow(source, sink);
+
+        let mut reachable = vec![false; self.len()];
+        let mut queue = VecDeque::from([source]);
+        reachable[source] = true;
+        while let Some(node) = queue.pop_front() {
+            for &edge in &self.outgoing[node] {
+                let FlowEdge { to, residual, .. } = self.edges[edge];
+                if residual > 0 && !reachable[to] {
+                    reachable[to] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        let crossing: Vec<usize> = (0..self.edges.len())
+            .filter(|&edge| {
+                let (from, to) = (self.edges[edge ^ 1].to, self.edges[edge].to);
+                self.edges[edge].capacity > 0 && reachable[from] && !reachable[to]
+            })
+            .collect();
+        let (source_side, sink_side) = (0..self.len()).partition(|&node| reachable[node]);
+
+        MinCut {
+            capacity: crossing.iter().map(|&edge| self.edges[edge].capacity).sum(),
+            source_side,
+            sink_side,
+            edges: crossing
+                .iter()
+                .map(|&edge| (self.edges[edge ^ 1].to, self.edges[edge].to))
+                .collect(),
+        }
+    }
+}
+
+/// Stoer–Wagner's algorithm for the lightest set of edges whose removal disconnects an
+/// undirected graph over the nodes `0..len`. Returns `None` with fewer than two nodes.
+pub fn global_min_cut(len: usize, edges: &[(usize, usize, u64)]) -> Option<MinCut> {
+    if len < 2 {
+        return None;
+    }
+
+    let mut adjacency: Vec<HashMap<usize, u64>> = vec![HashMap::new(); len];
+    for &(a, b, weight) in edges.iter().filter(|&&(a, b, _)| a != b) {
+        *adjacency[a].entry(b).or_default() += weight;
+        *adjacency[b].entry(a).or_default() += weight;
+    }
+    // The original nodes merged into each remaining one.
+    let mut members: Vec<Vec<usize>> = (0..len).map(|node| vec![node]).collect();
+    let mut active: Vec<usize> = (0..len).collect();
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // Maximum adjacency ordering: keep adding the node most tightly connected to the
+        // ones added so far. The last two are merged after recording the cut of the phase.
+        let mut connectivity = vec![0; len];
+        let mut added = vec![false; len];
+        let mut queue: BinaryHeap<(u64, usize)> = active.iter().map(|&node| (0, node)).collect();
+        let (mut previous, mut last) = (active[0], active[0]);
+        while let Some((weight, node)) = queue.pop() {
+            if added[node] || weight != connectivity[node] {
+                continue;
+            }
+
+            added[node] = true;
+            (previous, last) = (last, node);
+            for (&other, &w) in &adjacency[node] {
+                if !added[other] {
+                    connectivity[other] += w;
+                    queue.push((connectivity[other], other));
+                }
+            }
+        }
+
+        if best
+            .as_ref()
+            .is_none_or(|(weight, _)| connectivity[last] < *weight)
+        {
+            best = Some((connectivity[last], members[last].clone()));
+        }
+
+        let merged = std::mem::take(&mut adjacency[last]);
+        for (other, weight) in merged {
+            adjacency[other].remove(&last);
+            if other != previous {
+                *adjacency[previous].entry(other).or_default() += weight;
+                *adjacency[other].entry(previous).or_default() += weight;
+            }
+        }
+        let merged = std::mem::take(&mut members[last]);
+        members[previous].extend(merged);
+        active.retain(|&node| node != last);
+    }
+
+    let (capacity, mut source_side) = best?;
+    source_side.sort_unstable();
+    let mut on_source_side = vec![false; len];
+    source_side
+        .iter()
+        .for_each(|&node| on_source_side[node] = true);
+
+    Some(MinCut {
+        capacity,
+        sink_side: (0..len).filter(|&node| !on_source_side[node]).collect(),
+        edges: edges
+            .iter()
+            .filter(|&&(a, b, _)| on_source_side[a] != on_source_side[b])
+            .map(|&(a, b, _)| if on_source_side[a] { (a, b) } else { (b, a) })
+            .collect(),
+        source_side,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+        jqt: rhn xhk nvd\n\
+        rsh: frs pzl lsr\n\
+        xhk: hfx\n\
+        cmg: qnr nvd lhk bvb\n\
+        rhn: xhk bvb hfx\n\
+        bvb: xhk hfx\n\
+        pzl: lsr hfx nvd\n\
+        qnr: nvd\n\
+        ntq: jqt hfx bvb xhk\n\
+        nvd: lhk\n\
+        lsr: lhk\n\
+        rzs: qnr cmg lsr rsh\n\
+        frs: qnr lhk lsr\
+    ";
+
+    fn parse(input: &str) -> (Vec<&str>, Vec<(usize, usize, u64)>) {
+        let mut names: Vec<&str> = Vec::new();
+        let mut index = |name| match names.iter().position(|&other| other == name) {
+            Some(idx) => idx,
+            None => {
+                names.push(name);
+                names.len() - 1
+            }
+        };
+
+        let mut edges = Vec::new();
+        for line in input.lines() {
+            let (from, targets) = line.split_once(": ").unwrap();
+            let from = index(from);
+            for to in targets.split_whitespace() {
+                edges.push((from, index(to), 1));
+            }
+        }
+        (names, edges)
+    }
+
+    fn cut_wires<'a>(names: &[&'a str], cut: &MinCut) -> Vec<[&'a str; 2]> {
+        let mut wires: Vec<[&str; 2]> = cut
+            .edges
+            .iter()
+            .map(|&(a, b)| {
+                let mut wire = [names[a], names[b]];
+                wire.sort_unstable();
+                wire
+            })
+            .collect();
+        wires.sort_unstable();
+        wires
+    }
+
+    const EXPECTED_WIRES: [[&str; 2]; 3] = [["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]];
+
+    #[test]
+    fn test_min_cut() {
+        let (names, edges) = parse(INPUT);
+        let mut network = FlowNetwork::new(names.len());
+        for &(a, b, capacity) in &edges {
+            network.add_undirected_edge(a, b, capacity);
+        }
+
+        let source = names.iter().position(|&name| name == "cmg").unwrap();
+        let sink = names.iter().position(|&name| name == "xhk").unwrap();
+        let cut = network.min_cut(source, sink);
+
+        assert_eq!(3, cut.capacity);
+        assert_eq!((9, 6), (cut.source_side.len(), cut.sink_side.len()));
+        assert_eq!(EXPECTED_WIRES.to_vec(), cut_wires(&names, &cut));
+    }
+
+    #[test]
+    fn test_global_min_cut() {
+        let (names, edges) = parse(INPUT);
+        let cut = global_min_cut(names.len(), &edges).unwrap();
+
+        const EXPECTED: usize = 54;
+        assert_eq!(3, cut.capacity);
+        assert_eq!(EXPECTED, cut.source_side.len() * cut.sink_side.len());
+        assert_eq!(EXPECTED_WIRES.to_vec(), cut_wires(&names, &cut));
+    }
+
+    #[test]
+    fn test_max_flow() {
+        let mut network = FlowNetwork::new(4);
+        let edge = network.add_edge(0, 1, 3);
+        network.add_edge(0, 2, 2);
+        network.add_edge(1, 2, 5);
+        network.add_edge(1, 3, 2);
+        network.add_edge(2, 3, 3);
+
+        assert_eq!(5, network.max_flow(0, 3));
+        assert_eq!(3, network.flow(edge));
+        assert_eq!(0, network.max_flow(0, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "source and sink must be different nodes")]
+    fn test_max_flow_same_source_and_sink() {
+        let mut network = FlowNetwork::new(2);
+        network.add_edge(0, 1, 3);
+        network.max_flow(1, 1);
+    }
+}
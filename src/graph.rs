@@ -0,0 +1,366 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// A directed graph whose nodes are identified by their labels.
+#[derive(Debug, Clone)]
+pub struct DiGraph<L> {
+    labels: Vec<L>,
+    index: HashMap<L, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<L> Default for DiGraph<L> {
+    fn default() -> Self {
+        DiGraph {
+            labels: Vec::new(),
+            index: HashMap::new(),
+            successors: Vec::new(),
+            predecessors: Vec::new(),
+        }
+    }
+}
+
+impl<L: Clone + Eq + Hash> DiGraph<L> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses one rule per line, such as `A -> B` or `47|53`, with `separator` between the two
+    /// ends. Several targets can be listed, separated by commas (`A -> B, C`).
+    pub fn parse<E>(input: &str, separator: &str) -> Result<Self, E>
+    where
+        L: FromStr<Err = E>,
+    {
+        let mut graph = DiGraph::new();
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (from, targets) = line.split_once(separator).unwrap_or((line, ""));
+            let from: L = from.trim().parse()?;
+            graph.add_node(from.clone());
+            for to in targets
+                .split(',')
+                .map(str::trim)
+                .filter(|to| !to.is_empty())
+            {
+                graph.add_edge(from.clone(), to.parse()?);
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns the index of the node, adding it if needed.
+    pub fn add_node(&mut self, label: L) -> usize {
+        if let Some(&node) = self.index.get(&label) {
+            return node;
+        }
+
+        let node = self.labels.len();
+        self.index.insert(label.clone(), node);
+        self.labels.push(label);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        node
+    }
+
+    pub fn add_edge(&mut self, from: L, to: L) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        if !self.successors[from].contains(&to) {
+            self.successors[from].push(to);
+            self.predecessors[to].push(from);
+        }
+    }
+
+    pub fn node(&self, label: &L) -> Option<usize> {
+        self.index.get(label).copied()
+    }
+
+    pub fn label(&self, node: usize) -> &L {
+        &self.labels[node]
+    }
+
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    pub fn successors(&self, label: &L) -> impl Iterator<Item = &L> {
+        self.node(label)
+            .into_iter()
+            .flat_map(|node| self.successors[node].iter().map(|&next| &self.labels[next]))
+    }
+
+    pub fn predecessors(&self, label: &L) -> impl Iterator<Item = &L> {
+        self.node(label).into_iter().flat_map(|node| {
+            self.predecessors[node]
+                .iter()
+                .map(|&prev| &self.labels[prev])
+        })
+    }
+
+    pub fn contains_edge(&self, from: &L, to: &L) -> bool {
+        match (self.node(from), self.node(to)) {
+            (Some(from), Some(to)) => self.successors[from].contains(&to),
+            _ => false,
+        }
+    }
+
+    /// Orders every node after all of its predecessors, picking the smallest label whenever
+    /// several are available so that the result is deterministic. Fails with one of the
+    /// cycles if there is any.
+    pub fn topological_sort(&self) -> Result<Vec<L>, Vec<L>>
+    where
+        L: Ord,
+    {
+        let mut in_degrees: Vec<usize> = self.predecessors.iter().map(Vec::len).collect();
+        let mut available: BinaryHeap<Reverse<(&L, usize)>> = (0..self.len())
+            .filter(|&node| in_degrees[node] == 0)
+            .map(|node| Reverse((&self.labels[node], node)))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse((label, node))) = available.pop() {
+            order.push(label.clone());
+            for &next in &self.successors[node] {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    available.push(Reverse((&self.labels[next], next)));
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(self.find_cycle().expect("unsorted nodes lie on a cycle"))
+        }
+    }
+
+    /// Orders the given labels consistently with the edges between them, ignoring every other
+    /// node. Labels absent from the graph are unconstrained.
+    pub fn sort_subset(&self, subset: &[L]) -> Result<Vec<L>, Vec<L>>
+    where
+        L: Ord,
+    {
+        let mut graph = self.induced_subgraph(subset);
+        for label in subset {
+            graph.add_node(label.clone());
+        }
+        graph.topological_sort()
+    }
+
+    /// Whether no edge goes from a label to one placed before it.
+    pub fn is_sorted_subset(&self, order: &[L]) -> bool {
+        order.iter().enumerate().all(|(idx, later)| {
+            order[..idx]
+                .iter()
+                .all(|earlier| !self.contains_edge(later, earlier))
+        })
+    }
+
+    fn induced_subgraph(&self, subset: &[L]) -> DiGraph<L> {
+        let nodes: HashSet<usize> = subset.iter().filter_map(|label| self.node(label)).collect();
+        let mut graph = DiGraph::new();
+        for &from in &nodes {
+            graph.add_node(self.labels[from].clone());
+            for &to in self.successors[from].iter().filter(|to| nodes.contains(to)) {
+                graph.add_edge(self.labels[from].clone(), self.labels[to].clone());
+            }
+        }
+        graph
+    }
+
+    /// Returns the labels along one cycle, if any, without repeating the first one at the end.
+    pub fn find_cycle(&self) -> Option<Vec<L>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Mark {
+            Unvisited,
+            InProgress,
+            Done,
+        }
+
+        let mut marks = vec![Mark::Unvisited; self.len()];
+        for root in 0..self.len() {
+            if marks[root] != Mark::Unvisited {
+                continue;
+            }
+
+            // Iterative depth-first search; `path` holds the nodes in progress along with the
+            // index of the next successor to explore.
+            let mut path = vec![(root, 0)];
+            marks[root] = Mark::InProgress;
+            while let Some(&mut (node, ref mut next)) = path.last_mut() {
+                if let Some(&successor) = self.successors[node].get(*next) {
+                    *next += 1;
+                    match marks[successor] {
+                        Mark::Unvisited => {
+                            marks[successor] = Mark::InProgress;
+                            path.push((successor, 0));
+                        }
+                        Mark::InProgress => {
+                            let start = path.iter().position(|&(n, _)| n == successor).unwrap();
+                            return Some(
+                                path[start..]
+                                    .iter()
+                                    .map(|&(n, _)| self.labels[n].clone())
+                                    .collect(),
+                            );
+                        }
+                        Mark::Done => {}
+                    }
+                } else {
+                    marks[node] = Mark::Done;
+                    path.pop();
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Kosaraju's algorithm. Components come in topological order of the condensed graph, and
+    /// the labels within each component in the order they were added.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<L>> {
+        // First pass: record the nodes by increasing finishing time.
+        let mut finished = Vec::with_capacity(self.len());
+        let mut visited = vec![false; self.len()];
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+                if let Some(&successor) = self.successors[node].get(*next) {
+                    *next += 1;
+                    if !visited[successor] {
+                        visited[successor] = true;
+                        stack.push((successor, 0));
+                    }
+                } else {
+                    finished.push(node);
+                    stack.pop();
+                }
+            }
+        }
+
+        // Second pass: on the reversed graph, by decreasing finishing time.
+        let mut component = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for &root in finished.iter().rev() {
+            if component[root] != usize::MAX {
+                continue;
+            }
+            let id = components.len();
+            component[root] = id;
+            let mut members = vec![root];
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &previous in &self.predecessors[node] {
+                    if component[previous] == usize::MAX {
+                        component[previous] = id;
+                        members.push(previous);
+                        stack.push(previous);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(members);
+        }
+
+        components
+            .into_iter()
+            .map(|members| {
+                members
+                    .into_iter()
+                    .map(|n| self.labels[n].clone())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = "\
+        47|53\n\
+        97|13\n\
+        97|61\n\
+        97|47\n\
+        75|29\n\
+        61|13\n\
+        75|53\n\
+        29|13\n\
+        97|29\n\
+        53|29\n\
+        61|53\n\
+        97|53\n\
+        61|29\n\
+        47|13\n\
+        75|47\n\
+        97|75\n\
+        47|61\n\
+        75|61\n\
+        47|29\n\
+        75|13\n\
+        53|13\
+    ";
+
+    const UPDATES: [&[u32]; 6] = [
+        &[75, 47, 61, 53, 29],
+        &[97, 61, 53, 29, 13],
+        &[75, 29, 13],
+        &[75, 97, 47, 61, 53],
+        &[61, 13, 29],
+        &[97, 13, 75, 29, 47],
+    ];
+
+    #[test]
+    fn test_sort_subset() {
+        let graph: DiGraph<u32> = DiGraph::parse(RULES, "|").unwrap();
+
+        let (sorted, unsorted): (Vec<&[u32]>, Vec<&[u32]>) = UPDATES
+            .iter()
+            .partition(|update| graph.is_sorted_subset(update));
+        let result: u32 = sorted.iter().map(|update| update[update.len() / 2]).sum();
+        const EXPECTED: u32 = 143;
+        assert_eq!(EXPECTED, result);
+
+        let result: u32 = unsorted
+            .iter()
+            .map(|update| graph.sort_subset(update).unwrap())
+            .map(|update| update[update.len() / 2])
+            .sum();
+        const EXPECTED_FIXED: u32 = 123;
+        assert_eq!(EXPECTED_FIXED, result);
+    }
+
+    #[test]
+    fn test_cycles_and_components() {
+        let graph: DiGraph<char> =
+            DiGraph::parse("a -> b\nb -> c, d\nc -> a\nd -> e\ne", "->").unwrap();
+
+        assert_eq!(Some(vec!['a', 'b', 'c']), graph.find_cycle());
+        assert_eq!(Err(vec!['a', 'b', 'c']), graph.topological_sort());
+
+        let expected = vec![vec!['a', 'b', 'c'], vec!['d'], vec!['e']];
+        assert_eq!(expected, graph.strongly_connected_components());
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph: DiGraph<char> = DiGraph::parse("c -> a\nb -> a\na -> d", "->").unwrap();
+
+        assert_eq!(Ok(vec!['b', 'c', 'a', 'd']), graph.topological_sort());
+    }
+}
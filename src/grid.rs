@@ -1,9 +1,10 @@
-use std::cmp::Ordering;
 use std::fmt::Display;
-use std::iter::{Skip, StepBy, Take};
+use std::iter::StepBy;
 use std::slice::{Iter, IterMut};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawGrid<T>"))]
 pub struct Grid<T> {
     height: usize,
     width: usize,
@@ -32,11 +33,7 @@ impl<T> Grid<T> {
     }
 
     pub fn get(&self, position: Position) -> Option<&T> {
-        if position.x < self.width {
-            self.grid.get(position.x + position.y * self.width)
-        } else {
-            None
-        }
+        self.index_of(position).map(|index| &self.grid[index])
     }
 
     pub fn get_iter<I: IntoIterator<Item = Position>>(
@@ -47,86 +44,907 @@ impl<T> Grid<T> {
     }
 
     pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
-        if position.x < self.width {
-            self.grid.get_mut(position.x + position.y * self.width)
+        self.index_of(position).map(|index| &mut self.grid[index])
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        if position.x < self.width && position.y < self.height {
+            Some(position.x + position.y * self.width)
         } else {
             None
         }
     }
 
-    pub fn get_mut_tuple(
+    /// Returns mutable references to `N` distinct cells at once.
+    pub fn get_disjoint_mut<const N: usize>(
         &mut self,
-        positions: (Position, Position),
-    ) -> Option<(Option<&mut T>, Option<&mut T>)> {
-        if positions.0 == positions.1 {
-            return None;
+        positions: [Position; N],
+    ) -> Result<[&mut T; N], GetDisjointMutError> {
+        let mut indices = [0; N];
+        for (idx, &position) in positions.iter().enumerate() {
+            indices[idx] = self
+                .index_of(position)
+                .ok_or(GetDisjointMutError::OutOfBounds(position))?;
+            if positions[..idx].contains(&position) {
+                return Err(GetDisjointMutError::Overlapping(position));
+            }
         }
 
-        let p1 = positions.0.x + positions.0.y * self.width;
-        let p2 = positions.1.x + positions.1.y * self.width;
+        Ok(self
+            .grid
+            .get_disjoint_mut(indices)
+            .expect("indices are in bounds and distinct"))
+    }
 
-        match p1.cmp(&p2) {
-            Ordering::Equal => None,
-            Ordering::Greater => {
-                if p1 <= self.grid.len() {
-                    let (left, right) = self.grid.split_at_mut(p1);
-                    Some((right.first_mut(), left.get_mut(p2)))
-                } else {
-                    Some((None, self.grid.get_mut(p2)))
-                }
-            }
-            Ordering::Less => {
-                if p2 <= self.grid.len() {
-                    let (left, right) = self.grid.split_at_mut(p2);
-                    Some((left.get_mut(p1), right.first_mut()))
-                } else {
-                    Some((self.grid.get_mut(p1), None))
-                }
+    /// Swaps two cells; swapping a cell with itself does nothing.
+    pub fn swap(&mut self, a: Position, b: Position) -> Result<(), GetDisjointMutError> {
+        let a = self
+            .index_of(a)
+            .ok_or(GetDisjointMutError::OutOfBounds(a))?;
+        let b = self
+            .index_of(b)
+            .ok_or(GetDisjointMutError::OutOfBounds(b))?;
+        self.grid.swap(a, b);
+        Ok(())
+    }
+
+    pub fn row(&self, idy: usize) -> Option<&[T]> {
+        (idy < self.height).then(|| &self.grid[idy * self.width..(idy + 1) * self.width])
+    }
+
+    pub fn row_mut(&mut self, idy: usize) -> Option<&mut [T]> {
+        (idy < self.height).then(|| &mut self.grid[idy * self.width..(idy + 1) * self.width])
+    }
+
+    pub fn column(&self, idx: usize) -> Option<Line<'_, T>> {
+        (idx < self.width).then(|| Line::new(&self.grid, idx, self.width, self.height))
+    }
+
+    pub fn column_mut(&mut self, idx: usize) -> Option<LineMut<'_, T>> {
+        (idx < self.width).then(|| LineMut::new(&mut self.grid, idx, self.width, self.height))
+    }
+
+    // Returns the index of the first cell, the stride and the length of the diagonal going
+    // through `position`, from top left to bottom right (or top right to bottom left).
+    fn diagonal_bounds(&self, position: Position, anti: bool) -> Option<(usize, usize, usize)> {
+        self.index_of(position)?;
+        let (start, stride, len) = if anti {
+            let offset = position.y.min(self.width - 1 - position.x);
+            let start = Position::new(position.x + offset, position.y - offset);
+            let len = (start.x + 1).min(self.height - start.y);
+            (start, self.width - 1, len)
+        } else {
+            let offset = position.x.min(position.y);
+            let start = Position::new(position.x - offset, position.y - offset);
+            let len = (self.width - start.x).min(self.height - start.y);
+            (start, self.width + 1, len)
+        };
+        Some((start.x + start.y * self.width, stride, len))
+    }
+
+    /// The diagonal going through `position`, from its top left end to its bottom right end.
+    pub fn diagonal(&self, position: Position) -> Option<Line<'_, T>> {
+        let (start, stride, len) = self.diagonal_bounds(position, false)?;
+        Some(Line::new(&self.grid, start, stride, len))
+    }
+
+    pub fn diagonal_mut(&mut self, position: Position) -> Option<LineMut<'_, T>> {
+        let (start, stride, len) = self.diagonal_bounds(position, false)?;
+        Some(LineMut::new(&mut self.grid, start, stride, len))
+    }
+
+    /// The anti-diagonal going through `position`, from its top right end to its bottom left
+    /// end.
+    pub fn anti_diagonal(&self, position: Position) -> Option<Line<'_, T>> {
+        let (start, stride, len) = self.diagonal_bounds(position, true)?;
+        Some(Line::new(&self.grid, start, stride, len))
+    }
+
+    pub fn anti_diagonal_mut(&mut self, position: Position) -> Option<LineMut<'_, T>> {
+        let (start, stride, len) = self.diagonal_bounds(position, true)?;
+        Some(LineMut::new(&mut self.grid, start, stride, len))
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|idy| &self.grid[idy * self.width..(idy + 1) * self.width])
+    }
+
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> + ExactSizeIterator {
+        // Split the rows off one at a time rather than with `chunks_exact_mut`, which cannot
+        // take a zero width, so that a grid without columns still yields one row per line.
+        let mut rows = Vec::with_capacity(self.height);
+        let mut rest = self.grid.as_mut_slice();
+        for _ in 0..self.height {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(self.width);
+            rows.push(row);
+            rest = tail;
+        }
+        rows.into_iter()
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = Line<'_, T>> + ExactSizeIterator {
+        (0..self.width).map(|idx| Line::new(&self.grid, idx, self.width, self.height))
+    }
+
+    pub fn iter(&self) -> Cells<Iter<'_, T>> {
+        Cells::new(self.width, self.grid.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> Cells<IterMut<'_, T>> {
+        Cells::new(self.width, self.grid.iter_mut())
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |idy| (0..width).map(move |idx| Position::new(idx, idy)))
+    }
+
+    /// Builds a grid from its rows, or `None` if they do not all have the same length.
+    pub fn from_rows<R, I>(rows: R) -> Option<Self>
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut height = 0;
+        let mut width = None;
+        let mut grid = Vec::new();
+
+        for row in rows {
+            let before = grid.len();
+            grid.extend(row);
+            let length = grid.len() - before;
+            if *width.get_or_insert(length) != length {
+                return None;
             }
+            height += 1;
         }
+
+        Grid::new(height, width.unwrap_or_default(), grid)
     }
 
-    pub fn row(&self, idy: usize) -> Take<Skip<Iter<'_, T>>> {
-        self.grid.iter().skip(idy * self.width).take(self.width)
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            grid: self.grid.iter().map(f).collect(),
+        }
     }
 
-    pub fn row_mut(&mut self, idy: usize) -> Take<Skip<IterMut<'_, T>>> {
-        self.grid.iter_mut().skip(idy * self.width).take(self.width)
+    /// Combines two grids cell by cell, or returns `None` if their dimensions differ.
+    pub fn zip_with<U, V, F: FnMut(&T, &U) -> V>(
+        &self,
+        other: &Grid<U>,
+        mut f: F,
+    ) -> Option<Grid<V>> {
+        if (self.height, self.width) != (other.height, other.width) {
+            return None;
+        }
+
+        Some(Grid {
+            height: self.height,
+            width: self.width,
+            grid: self
+                .grid
+                .iter()
+                .zip(&other.grid)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        })
     }
 
-    pub fn column(&self, idx: usize) -> Take<StepBy<Skip<Iter<'_, T>>>> {
-        self.grid
-            .iter()
-            .skip(idx)
-            .step_by(self.width)
-            .take(self.height)
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.grid.fill(value);
+    }
+
+    pub fn count<F: Fn(&T) -> bool>(&self, predicate: F) -> usize {
+        self.grid.iter().filter(|value| predicate(value)).count()
+    }
+
+    /// Returns the first matching position, in row-major order.
+    pub fn find<F: Fn(&T) -> bool>(&self, predicate: F) -> Option<Position> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(position, _)| position)
+    }
+
+    /// The cells met when walking from `start` (excluded) in `direction`, until leaving the
+    /// grid.
+    pub fn ray(
+        &self,
+        start: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        start
+            .line(direction)
+            .skip(1)
+            .map_while(|p| self.get(p).map(|value| (p, value)))
+    }
+
+    /// Returns the first cell along the ray matching `predicate`, with its distance in steps.
+    pub fn cast_ray<F: Fn(&T) -> bool>(
+        &self,
+        start: Position,
+        direction: Direction,
+        predicate: F,
+    ) -> Option<(Position, usize)> {
+        self.ray(start, direction)
+            .zip(1..)
+            .find(|((_, value), _)| predicate(value))
+            .map(|((position, _), distance)| (position, distance))
+    }
+
+    /// Casts a ray in each of the eight `DIRECTIONS`, returning the directions in which a
+    /// matching cell is visible along with the first such cell and its distance.
+    pub fn visible<F: Fn(&T) -> bool>(
+        &self,
+        start: Position,
+        predicate: F,
+    ) -> impl Iterator<Item = (Direction, Position, usize)> {
+        DIRECTIONS.into_iter().filter_map(move |direction| {
+            self.cast_ray(start, direction, &predicate)
+                .map(|(position, distance)| (direction, position, distance))
+        })
+    }
+
+    /// Whether no cell strictly between `from` and `to` (along their rasterised line) matches
+    /// `blocks`.
+    pub fn line_of_sight<F: Fn(&T) -> bool>(
+        &self,
+        from: Position,
+        to: Position,
+        blocks: F,
+    ) -> bool {
+        from.line_to(to)
+            .filter(|&p| p != from && p != to)
+            .all(|p| self.get(p).is_some_and(|value| !blocks(value)))
+    }
+
+    /// Returns a rotated or reflected copy of the grid.
+    pub fn transform(&self, transform: Transform) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (h, w) = (self.height, self.width);
+        let (height, width) = if transform.swaps_dimensions() {
+            (w, h)
+        } else {
+            (h, w)
+        };
+
+        let grid = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = match transform {
+                    Transform::Identity => (x, y),
+                    Transform::Rotate90 => (y, h - 1 - x),
+                    Transform::Rotate180 => (w - 1 - x, h - 1 - y),
+                    Transform::Rotate270 => (w - 1 - y, x),
+                    Transform::FlipHorizontal => (w - 1 - x, y),
+                    Transform::FlipVertical => (x, h - 1 - y),
+                    Transform::Transpose => (y, x),
+                    Transform::AntiTranspose => (w - 1 - y, h - 1 - x),
+                };
+                self.grid[sx + sy * w].clone()
+            })
+            .collect();
+
+        Grid {
+            height,
+            width,
+            grid,
+        }
+    }
+
+    /// Panics if `idy > height` or if the row does not match the width (an empty grid takes
+    /// the width of its first row).
+    pub fn insert_row(&mut self, idy: usize, row: Vec<T>) {
+        assert!(idy <= self.height, "row index {} out of bounds", idy);
+        if self.height == 0 && self.width == 0 {
+            self.width = row.len();
+        }
+        assert_eq!(self.width, row.len(), "row length does not match the width");
+
+        let start = idy * self.width;
+        self.grid.splice(start..start, row);
+        self.height += 1;
+    }
+
+    /// Panics if `idx > width` or if the column does not match the height (an empty grid takes
+    /// the height of its first column).
+    pub fn insert_column(&mut self, idx: usize, column: Vec<T>) {
+        assert!(idx <= self.width, "column index {} out of bounds", idx);
+        if self.height == 0 && self.width == 0 {
+            self.height = column.len();
+        }
+        assert_eq!(
+            self.height,
+            column.len(),
+            "column length does not match the height"
+        );
+
+        let mut old = std::mem::take(&mut self.grid).into_iter();
+        self.grid.reserve_exact(self.height * (self.width + 1));
+        for value in column {
+            self.grid.extend(old.by_ref().take(idx));
+            self.grid.push(value);
+            self.grid.extend(old.by_ref().take(self.width - idx));
+        }
+        self.width += 1;
     }
 
-    pub fn column_mut(&mut self, idx: usize) -> Take<StepBy<Skip<IterMut<'_, T>>>> {
+    pub fn remove_row(&mut self, idy: usize) -> Option<Vec<T>> {
+        if idy >= self.height {
+            return None;
+        }
+
+        let start = idy * self.width;
+        let row = self.grid.drain(start..start + self.width).collect();
+        self.height -= 1;
+        Some(row)
+    }
+
+    pub fn remove_column(&mut self, idx: usize) -> Option<Vec<T>> {
+        if idx >= self.width {
+            return None;
+        }
+
+        let old = std::mem::take(&mut self.grid);
+        let mut column = Vec::with_capacity(self.height);
+        self.grid.reserve_exact(self.height * (self.width - 1));
+        for (index, value) in old.into_iter().enumerate() {
+            if index % self.width == idx {
+                column.push(value);
+            } else {
+                self.grid.push(value);
+            }
+        }
+        self.width -= 1;
+        Some(column)
+    }
+
+    /// Surrounds the grid with `border` rows and columns of `value` on every side.
+    pub fn pad(&mut self, border: usize, value: T)
+    where
+        T: Clone,
+    {
+        let height = self.height + 2 * border;
+        let width = self.width + 2 * border;
+        let mut old = std::mem::take(&mut self.grid).into_iter();
+
+        self.grid.reserve_exact(height * width);
         self.grid
-            .iter_mut()
-            .skip(idx)
-            .step_by(self.width)
-            .take(self.height)
+            .extend(std::iter::repeat_n(value.clone(), border * width));
+        for _ in 0..self.height {
+            self.grid.extend(std::iter::repeat_n(value.clone(), border));
+            self.grid.extend(old.by_ref().take(self.width));
+            self.grid.extend(std::iter::repeat_n(value.clone(), border));
+        }
+        self.grid.extend(std::iter::repeat_n(value, border * width));
+
+        self.height = height;
+        self.width = width;
     }
 
-    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
-        (0..self.height).map(|idy| self.row(idy))
+    /// Keeps only the `height` x `width` rectangle starting at `top_left`. Panics if the
+    /// rectangle does not fit within the grid.
+    pub fn crop(&mut self, top_left: Position, height: usize, width: usize) {
+        assert!(
+            top_left.y + height <= self.height && top_left.x + width <= self.width,
+            "crop rectangle out of bounds"
+        );
+
+        let old = std::mem::take(&mut self.grid);
+        let old_width = self.width;
+        self.grid = old
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                let (idx, idy) = (index % old_width, index / old_width);
+                (top_left.x..top_left.x + width).contains(&idx)
+                    && (top_left.y..top_left.y + height).contains(&idy)
+            })
+            .map(|(_, value)| value)
+            .collect();
+        self.height = height;
+        self.width = width;
     }
 
-    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
-        (0..self.width).map(|idx| self.column(idx))
+    /// Returns the top left and bottom right corners (inclusive) of the smallest rectangle
+    /// containing every cell matching the predicate.
+    pub fn bounding_box<F: Fn(&T) -> bool>(&self, predicate: F) -> Option<(Position, Position)> {
+        self.iter().filter(|(_, value)| predicate(value)).fold(
+            None,
+            |bounds, (p, _)| match bounds {
+                None => Some((p, p)),
+                Some((min, max)) => Some((
+                    Position::new(min.x.min(p.x), min.y.min(p.y)),
+                    Position::new(max.x.max(p.x), max.y.max(p.y)),
+                )),
+            },
+        )
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
-        self.rows().enumerate().flat_map(|(idy, row)| {
-            row.enumerate()
-                .map(move |(idx, value)| (Position::new(idx, idy), value))
+    /// Crops the grid to the bounding box of the cells matching the predicate, returning the
+    /// position of the new top left corner in the old grid. Leaves the grid untouched if no
+    /// cell matches.
+    pub fn crop_to<F: Fn(&T) -> bool>(&mut self, predicate: F) -> Option<Position> {
+        let (min, max) = self.bounding_box(predicate)?;
+        self.crop(min, max.y - min.y + 1, max.x - min.x + 1);
+        Some(min)
+    }
+}
+
+// Deserialized grids go through `Grid::new`, so that the dimensions always match the cells.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawGrid<T> {
+    height: usize,
+    width: usize,
+    grid: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawGrid<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(raw: RawGrid<T>) -> Result<Self, Self::Error> {
+        let cells = raw.grid.len();
+        Grid::new(raw.height, raw.width, raw.grid).ok_or_else(|| {
+            format!(
+                "{} cells do not fill a {}x{} grid",
+                cells, raw.width, raw.height
+            )
+        })
+    }
+}
+
+/// Compact textual representation of a grid, one line of characters per row, for use with
+/// `#[serde(with = "advent_of_code_2025::grid::serde_text")]`. The text cannot tell how many
+/// rows or columns an empty grid has, so only the 0x0 one can be serialized.
+#[cfg(feature = "serde")]
+pub mod serde_text {
+    use super::Grid;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(grid: &Grid<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Clone + Into<char>,
+        S: Serializer,
+    {
+        if (grid.width == 0) != (grid.height == 0) {
+            return Err(serde::ser::Error::custom(format!(
+                "a {}x{} grid has no text representation",
+                grid.width, grid.height
+            )));
+        }
+        let text = super::render(grid, |_, value| value.cloned().unwrap().into());
+        serializer.serialize_str(&text)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Grid<T>, D::Error>
+    where
+        T: TryFrom<char>,
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        let rows = text
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| {
+                        T::try_from(c)
+                            .map_err(|_| D::Error::custom(format!("invalid cell {:?}", c)))
+                    })
+                    .collect::<Result<Vec<T>, _>>()
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+        Grid::from_rows(rows).ok_or_else(|| D::Error::custom("rows of different lengths"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GetDisjointMutError {
+    OutOfBounds(Position),
+    Overlapping(Position),
+}
+
+impl Display for GetDisjointMutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GetDisjointMutError::OutOfBounds(position) => {
+                write!(f, "position {} is out of bounds", position)
+            }
+            GetDisjointMutError::Overlapping(position) => {
+                write!(f, "position {} is requested more than once", position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GetDisjointMutError {}
+
+impl<T> std::ops::Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        let (height, width) = (self.height, self.width);
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {} out of bounds for a {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+impl<T> std::ops::IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {} out of bounds for a {}x{} grid",
+                position, width, height
+            )
         })
     }
 }
 
+impl<T> IntoIterator for Grid<T> {
+    type Item = (Position, T);
+    type IntoIter = Cells<std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Cells::new(self.width, self.grid.into_iter())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = (Position, &'a T);
+    type IntoIter = Cells<Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Grid<T> {
+    type Item = (Position, &'a mut T);
+    type IntoIter = Cells<IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Collecting rows panics if they do not all have the same length; use `Grid::from_rows` to get
+// an `Option` instead.
+impl<T, I: IntoIterator<Item = T>> FromIterator<I> for Grid<T> {
+    fn from_iter<R: IntoIterator<Item = I>>(rows: R) -> Self {
+        Grid::from_rows(rows).expect("rows of different lengths")
+    }
+}
+
+/// Iterator over the cells of a grid in row-major order, along with their positions.
+#[derive(Debug, Clone)]
+pub struct Cells<I> {
+    width: usize,
+    inner: std::iter::Enumerate<I>,
+}
+
+impl<I: Iterator> Cells<I> {
+    fn new(width: usize, inner: I) -> Self {
+        Cells {
+            width,
+            inner: inner.enumerate(),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Cells<I> {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(index, value)| (Position::new(index % self.width, index / self.width), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Cells<I> {}
+
+/// A column or diagonal of a grid: `len` cells taken at a fixed stride through the row-major
+/// storage.
+#[derive(Debug)]
+pub struct Line<'a, T> {
+    cells: &'a [T],
+    stride: usize,
+}
+
+impl<'a, T> Line<'a, T> {
+    fn new(grid: &'a [T], start: usize, stride: usize, len: usize) -> Self {
+        // Trim the storage to end on the last cell, so that stepping through it yields exactly
+        // `len` cells from either end.
+        // An empty line may start past the end, as do the columns of a grid without rows.
+        let cells = if len == 0 {
+            &grid[..0]
+        } else {
+            &grid[start..start + (len - 1) * stride + 1]
+        };
+        Line {
+            cells,
+            stride: stride.max(1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len().div_ceil(self.stride)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len() {
+            Some(&self.cells[index * self.stride])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> StepBy<Iter<'a, T>> {
+        self.cells.iter().step_by(self.stride)
+    }
+}
+
+impl<T> Clone for Line<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Line<'_, T> {}
+
+impl<T> std::ops::Index<usize> for Line<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let len = self.len();
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {} out of bounds for a line of {}", index, len))
+    }
+}
+
+impl<'a, T> IntoIterator for Line<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StepBy<Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug)]
+pub struct LineMut<'a, T> {
+    cells: &'a mut [T],
+    stride: usize,
+}
+
+impl<'a, T> LineMut<'a, T> {
+    fn new(grid: &'a mut [T], start: usize, stride: usize, len: usize) -> Self {
+        let cells = if len == 0 {
+            &mut grid[..0]
+        } else {
+            &mut grid[start..start + (len - 1) * stride + 1]
+        };
+        LineMut {
+            cells,
+            stride: stride.max(1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len().div_ceil(self.stride)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            Some(&self.cells[index * self.stride])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            Some(&mut self.cells[index * self.stride])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> StepBy<Iter<'_, T>> {
+        self.cells.iter().step_by(self.stride)
+    }
+
+    pub fn iter_mut(&mut self) -> StepBy<IterMut<'_, T>> {
+        self.cells.iter_mut().step_by(self.stride)
+    }
+}
+
+impl<T> std::ops::Index<usize> for LineMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let len = self.len();
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {} out of bounds for a line of {}", index, len))
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for LineMut<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len();
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("index {} out of bounds for a line of {}", index, len))
+    }
+}
+
+impl<'a, T> IntoIterator for LineMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = StepBy<IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter_mut().step_by(self.stride)
+    }
+}
+
+/// The eight symmetries of a rectangle; rotations are clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+pub const TRANSFORMS: [Transform; 8] = [
+    Transform::Identity,
+    Transform::Rotate90,
+    Transform::Rotate180,
+    Transform::Rotate270,
+    Transform::FlipHorizontal,
+    Transform::FlipVertical,
+    Transform::Transpose,
+    Transform::AntiTranspose,
+];
+
+impl Transform {
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+}
+
+/// Read access shared by the dense, sparse and bit-packed grids, so that algorithms can be
+/// written once for all of them.
+pub trait GridLike {
+    type Item;
+
+    fn height(&self) -> usize;
+
+    fn width(&self) -> usize;
+
+    fn get(&self, position: Position) -> Option<&Self::Item>;
+
+    /// Iterates over the occupied cells: every cell of a dense grid, but only the stored ones
+    /// of a sparse grid.
+    fn cells(&self) -> impl Iterator<Item = (Position, &Self::Item)>;
+
+    fn contains(&self, position: Position) -> bool {
+        position.x < self.width() && position.y < self.height()
+    }
+
+    fn neighbours(&self, position: Position) -> impl Iterator<Item = (Position, &Self::Item)> {
+        position
+            .neighbours()
+            .filter_map(|p| self.get(p).map(|value| (p, value)))
+    }
+
+    fn extended_neighbours(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = (Position, &Self::Item)> {
+        position
+            .extended_neighbours()
+            .filter_map(|p| self.get(p).map(|value| (p, value)))
+    }
+
+    fn within_manhattan(&self, center: Position, radius: usize) -> impl Iterator<Item = Position> {
+        center
+            .within_manhattan(radius)
+            .filter(|&p| self.contains(p))
+    }
+
+    fn within_chebyshev(&self, center: Position, radius: usize) -> impl Iterator<Item = Position> {
+        center
+            .within_chebyshev(radius)
+            .filter(|&p| self.contains(p))
+    }
+}
+
+pub trait GridLikeMut: GridLike {
+    fn get_mut(&mut self, position: Position) -> Option<&mut Self::Item>;
+}
+
+impl<T> GridLike for Grid<T> {
+    type Item = T;
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn get(&self, position: Position) -> Option<&T> {
+        Grid::get(self, position)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.iter()
+    }
+}
+
+impl<T> GridLikeMut for Grid<T> {
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        Grid::get_mut(self, position)
+    }
+}
+
+/// Renders one character per cell, with `None` for the cells a sparse grid does not store.
+pub fn render<G, F>(grid: &G, f: F) -> String
+where
+    G: GridLike,
+    F: Fn(Position, Option<&G::Item>) -> char,
+{
+    let mut output = String::with_capacity(grid.height() * (grid.width() + 1));
+    for idy in 0..grid.height() {
+        if idy != 0 {
+            output.push('\n');
+        }
+        for idx in 0..grid.width() {
+            let position = Position::new(idx, idy);
+            output.push(f(position, grid.get(position)));
+        }
+    }
+    output
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     y: usize,
     x: usize,
@@ -170,9 +988,65 @@ impl Position {
         self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    pub fn squared_euclidean_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).pow(2) + self.y.abs_diff(other.y).pow(2)
+    }
+
+    /// Positions within `radius` in Manhattan distance (a diamond), row by row, skipping those
+    /// that would have a negative coordinate.
+    pub fn within_manhattan(self, radius: usize) -> impl Iterator<Item = Position> {
+        let radius = radius as isize;
+        (-radius..=radius).flat_map(move |dy| {
+            let remaining = radius - dy.abs();
+            (-remaining..=remaining).filter_map(move |dx| self + (dx, dy))
+        })
+    }
+
+    /// Positions within `radius` in Chebyshev distance (a square), row by row, skipping those
+    /// that would have a negative coordinate.
+    pub fn within_chebyshev(self, radius: usize) -> impl Iterator<Item = Position> {
+        let (min_x, max_x) = (self.x.saturating_sub(radius), self.x + radius);
+        let (min_y, max_y) = (self.y.saturating_sub(radius), self.y + radius);
+        (min_y..=max_y).flat_map(move |idy| (min_x..=max_x).map(move |idx| Position::new(idx, idy)))
+    }
+
     pub fn line(self, direction: Direction) -> impl std::iter::Iterator<Item = Position> {
         std::iter::successors(Some(self), move |&p| p + direction)
     }
+
+    /// Rasterises the straight line from `self` to `other`, both included, using Bresenham's
+    /// algorithm. Every position lies within the rectangle spanned by the two ends.
+    pub fn line_to(self, other: Self) -> impl Iterator<Item = Position> {
+        let (dx, dy) = other - self;
+        let (step_x, step_y) = (dx.signum(), dy.signum());
+        let (dx, dy) = (dx.abs(), -dy.abs());
+        let mut error = dx + dy;
+        let mut current = Some(self);
+
+        std::iter::from_fn(move || {
+            let position = current?;
+            current = if position == other {
+                None
+            } else {
+                let (mut x, mut y) = (position.x as isize, position.y as isize);
+                let doubled = 2 * error;
+                if doubled >= dy {
+                    error += dy;
+                    x += step_x;
+                }
+                if doubled <= dx {
+                    error += dx;
+                    y += step_y;
+                }
+                Some(Position::new(x as usize, y as usize))
+            };
+            Some(position)
+        })
+    }
 }
 
 impl std::ops::Add<Direction> for Position {
@@ -195,6 +1069,27 @@ impl std::ops::Add<Direction> for Position {
     }
 }
 
+impl std::ops::Add<(isize, isize)> for Position {
+    type Output = Option<Self>;
+
+    fn add(self, rhs: (isize, isize)) -> Self::Output {
+        let x = self.x.checked_add_signed(rhs.0)?;
+        let y = self.y.checked_add_signed(rhs.1)?;
+        Some(Position::new(x, y))
+    }
+}
+
+impl std::ops::Sub for Position {
+    type Output = (isize, isize);
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        (
+            self.x as isize - rhs.x as isize,
+            self.y as isize - rhs.y as isize,
+        )
+    }
+}
+
 impl Display for Position {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
@@ -202,6 +1097,7 @@ impl Display for Position {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Up,
     Down,
@@ -224,7 +1120,38 @@ pub const DIRECTIONS: [Direction; 8] = [
     Direction::DownRight,
 ];
 
+pub const CARDINAL_DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+pub const DIAGONAL_DIRECTIONS: [Direction; 4] = [
+    Direction::UpLeft,
+    Direction::DownLeft,
+    Direction::UpRight,
+    Direction::DownRight,
+];
+
 impl Direction {
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::UpRight => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        DIAGONAL_DIRECTIONS.contains(&self)
+    }
+
     pub fn rotate_left(self) -> Self {
         match self {
             Direction::Up => Direction::Left,
@@ -251,6 +1178,32 @@ impl Direction {
         }
     }
 
+    pub fn rotate_left_45(self) -> Self {
+        match self {
+            Direction::Up => Direction::UpLeft,
+            Direction::Down => Direction::DownRight,
+            Direction::Left => Direction::DownLeft,
+            Direction::Right => Direction::UpRight,
+            Direction::UpLeft => Direction::Left,
+            Direction::DownLeft => Direction::Down,
+            Direction::UpRight => Direction::Up,
+            Direction::DownRight => Direction::Right,
+        }
+    }
+
+    pub fn rotate_right_45(self) -> Self {
+        match self {
+            Direction::Up => Direction::UpRight,
+            Direction::Down => Direction::DownLeft,
+            Direction::Left => Direction::UpLeft,
+            Direction::Right => Direction::DownRight,
+            Direction::UpLeft => Direction::Up,
+            Direction::DownLeft => Direction::Left,
+            Direction::UpRight => Direction::Right,
+            Direction::DownRight => Direction::Down,
+        }
+    }
+
     pub fn reverse(self) -> Self {
         match self {
             Direction::Up => Direction::Down,
@@ -264,3 +1217,425 @@ impl Direction {
         }
     }
 }
+
+impl std::ops::Mul<usize> for Direction {
+    type Output = (isize, isize);
+
+    fn mul(self, rhs: usize) -> Self::Output {
+        let (dx, dy) = self.delta();
+        (dx * rhs as isize, dy * rhs as isize)
+    }
+}
+
+// Accepts arrows (`^v<>`), initials (`UDLR`) and compass points (`NSEW`), with up being north.
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            _ => Err(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_grid() -> Grid<u32> {
+        Grid::new(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap()
+    }
+
+    #[test]
+    fn test_insert_remove_row() {
+        let mut grid = example_grid();
+        grid.insert_row(1, vec![7, 8, 9]);
+        assert_eq!(
+            Grid::new(3, 3, vec![1, 2, 3, 7, 8, 9, 4, 5, 6]),
+            Some(grid.clone())
+        );
+
+        assert_eq!(Some(vec![7, 8, 9]), grid.remove_row(1));
+        assert_eq!(None, grid.remove_row(2));
+        assert_eq!(example_grid(), grid);
+    }
+
+    #[test]
+    fn test_insert_remove_column() {
+        let mut grid = example_grid();
+        grid.insert_column(3, vec![7, 8]);
+        assert_eq!(
+            Grid::new(2, 4, vec![1, 2, 3, 7, 4, 5, 6, 8]),
+            Some(grid.clone())
+        );
+
+        assert_eq!(Some(vec![7, 8]), grid.remove_column(3));
+        assert_eq!(None, grid.remove_column(3));
+        assert_eq!(example_grid(), grid);
+    }
+
+    #[test]
+    fn test_pad_and_crop_to() {
+        let mut grid = example_grid();
+        grid.pad(1, 0);
+        const EXPECTED: [u32; 20] = [0, 0, 0, 0, 0, 0, 1, 2, 3, 0, 0, 4, 5, 6, 0, 0, 0, 0, 0, 0];
+        assert_eq!(Grid::new(4, 5, EXPECTED.to_vec()), Some(grid.clone()));
+
+        assert_eq!(Some(Position::new(1, 1)), grid.crop_to(|&v| v != 0));
+        assert_eq!(example_grid(), grid);
+    }
+
+    #[test]
+    fn test_render() {
+        let result = render(&example_grid(), |_, value| {
+            char::from_digit(*value.unwrap(), 10).unwrap()
+        });
+        const EXPECTED: &str = "123\n456";
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut grid = example_grid();
+        grid[Position::new(2, 1)] = 9;
+        assert_eq!(9, grid[Position::new(2, 1)]);
+        assert_eq!(None, grid.get(Position::new(3, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_out_of_bounds() {
+        let grid = example_grid();
+        let _ = grid[Position::new(3, 0)];
+    }
+
+    #[test]
+    fn test_from_iterator_and_into_iterator() {
+        let grid: Grid<u32> = [[1, 2, 3], [4, 5, 6]].into_iter().collect();
+        assert_eq!(example_grid(), grid);
+        assert_eq!(None, Grid::from_rows([vec![1, 2], vec![3]]));
+
+        let result: Vec<(Position, u32)> = grid.into_iter().skip(2).take(2).collect();
+        let expected = vec![(Position::new(2, 0), 3), (Position::new(0, 1), 4)];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_map_zip_with_count_find() {
+        let grid = example_grid();
+        let doubled = grid.map(|v| v * 2);
+        let result = grid.zip_with(&doubled, |a, b| a + b).unwrap();
+        assert_eq!(grid.map(|v| v * 3), result);
+
+        assert_eq!(3, result.count(|v| v % 2 == 0));
+        assert_eq!(Some(Position::new(1, 1)), result.find(|&v| v > 12));
+        assert_eq!(
+            None,
+            grid.zip_with(&Grid::new(0, 0, Vec::<u32>::new()).unwrap(), |a, _| *a)
+        );
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut grid = example_grid();
+        let [a, b, c] = grid
+            .get_disjoint_mut([
+                Position::new(0, 0),
+                Position::new(2, 1),
+                Position::new(1, 0),
+            ])
+            .unwrap();
+        std::mem::swap(a, b);
+        *c = 0;
+        assert_eq!(Grid::new(2, 3, vec![6, 0, 3, 4, 5, 1]), Some(grid.clone()));
+
+        let result = grid.get_disjoint_mut([Position::new(1, 1), Position::new(1, 1)]);
+        assert_eq!(
+            Err(GetDisjointMutError::Overlapping(Position::new(1, 1))),
+            result
+        );
+        // An `x` past the width must not wrap around to the next row.
+        let result = grid.get_disjoint_mut([Position::new(0, 0), Position::new(3, 0)]);
+        assert_eq!(
+            Err(GetDisjointMutError::OutOfBounds(Position::new(3, 0))),
+            result
+        );
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut grid = example_grid();
+        grid.swap(Position::new(0, 0), Position::new(2, 1)).unwrap();
+        assert_eq!(Grid::new(2, 3, vec![6, 2, 3, 4, 5, 1]), Some(grid.clone()));
+        assert!(grid.swap(Position::new(0, 0), Position::new(0, 2)).is_err());
+    }
+
+    #[test]
+    fn test_row_and_column() {
+        let mut grid = example_grid();
+        assert_eq!(Some([4, 5, 6].as_slice()), grid.row(1));
+        assert_eq!(None, grid.row(2));
+
+        let column = grid.column(1).unwrap();
+        assert_eq!(2, column.len());
+        assert_eq!(5, column[1]);
+        assert_eq!(vec![&5, &2], column.iter().rev().collect::<Vec<_>>());
+        assert!(grid.column(3).is_none());
+
+        grid.column_mut(2).unwrap()[0] = 0;
+        grid.row_mut(1).unwrap()[1..].fill(9);
+        assert_eq!(Grid::new(2, 3, vec![1, 2, 0, 4, 9, 9]), Some(grid));
+    }
+
+    #[test]
+    fn test_rows_mut() {
+        let mut grid = example_grid();
+        grid.rows_mut().next_back().unwrap()[0] = 0;
+        assert_eq!(Grid::new(2, 3, vec![1, 2, 3, 0, 5, 6]), Some(grid));
+
+        // Removing every column leaves a grid with rows but no cells.
+        let mut grid = example_grid();
+        for _ in 0..3 {
+            grid.remove_column(0);
+        }
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(2, grid.rows_mut().len());
+        assert!(grid.rows_mut().all(|row| row.is_empty()));
+    }
+
+    #[test]
+    fn test_columns_without_rows() {
+        // Removing every row leaves a grid with columns but no cells.
+        let mut grid = example_grid();
+        for _ in 0..2 {
+            grid.remove_row(0);
+        }
+        assert!(grid.column(1).unwrap().is_empty());
+        assert!(grid.column_mut(2).unwrap().is_empty());
+        assert_eq!(3, grid.columns().count());
+        assert!(grid.columns().all(|column| column.is_empty()));
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = Grid::new(3, 4, (0..12).collect()).unwrap();
+        let result: Vec<u32> = grid
+            .diagonal(Position::new(2, 1))
+            .unwrap()
+            .into_iter()
+            .copied()
+            .collect();
+        assert_eq!(vec![1, 6, 11], result);
+
+        let result: Vec<u32> = grid
+            .anti_diagonal(Position::new(1, 1))
+            .unwrap()
+            .into_iter()
+            .rev()
+            .copied()
+            .collect();
+        assert_eq!(vec![8, 5, 2], result);
+
+        let result = grid.anti_diagonal(Position::new(3, 2)).unwrap();
+        assert_eq!((1, Some(&11)), (result.len(), result.get(0)));
+        assert!(grid.diagonal(Position::new(4, 0)).is_none());
+    }
+
+    #[test]
+    fn test_cast_ray_and_visible() {
+        let grid: Grid<char> = ["#..#", "....", "...#"]
+            .iter()
+            .map(|row| row.chars())
+            .collect();
+        let start = Position::new(0, 2);
+
+        assert_eq!(2, grid.ray(start, Direction::Up).count());
+        assert_eq!(
+            Some((Position::new(3, 2), 3)),
+            grid.cast_ray(start, Direction::Right, |&c| c == '#')
+        );
+        assert_eq!(
+            None,
+            grid.cast_ray(start, Direction::UpRight, |&c| c == '#')
+        );
+
+        let result: Vec<(Direction, Position, usize)> =
+            grid.visible(start, |&c| c == '#').collect();
+        let expected = vec![
+            (Direction::Up, Position::new(0, 0), 2),
+            (Direction::Right, Position::new(3, 2), 3),
+        ];
+        assert_eq!(expected, result);
+
+        assert!(grid.line_of_sight(start, Position::new(3, 0), |&c| c == '#'));
+        assert!(!grid.line_of_sight(Position::new(0, 0), Position::new(3, 0), |&c| c == '.'));
+    }
+
+    #[test]
+    fn test_line_to() {
+        let result: Vec<Position> = Position::new(0, 0).line_to(Position::new(5, 2)).collect();
+        let expected: Vec<Position> = [(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]
+            .into_iter()
+            .map(|(x, y)| Position::new(x, y))
+            .collect();
+        assert_eq!(expected, result);
+
+        let reverse: Vec<Position> = Position::new(5, 2).line_to(Position::new(0, 0)).collect();
+        assert_eq!(6, reverse.len());
+        assert_eq!(
+            vec![Position::new(2, 2)],
+            Position::new(2, 2)
+                .line_to(Position::new(2, 2))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Position::new(1, 5), Position::new(4, 1));
+        assert_eq!(7, a.manhattan_distance(b));
+        assert_eq!(4, a.chebyshev_distance(b));
+        assert_eq!(25, a.squared_euclidean_distance(b));
+    }
+
+    #[test]
+    fn test_within_radius() {
+        let center = Position::new(1, 1);
+        assert_eq!(13, Position::new(5, 5).within_manhattan(2).count());
+        assert!(
+            center
+                .within_manhattan(2)
+                .all(|p| p.manhattan_distance(center) <= 2)
+        );
+        assert_eq!(11, center.within_manhattan(2).count());
+        assert_eq!(16, center.within_chebyshev(2).count());
+
+        let grid = example_grid();
+        assert_eq!(6, grid.within_manhattan(center, 2).count());
+        assert_eq!(6, grid.within_chebyshev(center, 2).count());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let grid = example_grid();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(r#"{"height":2,"width":3,"grid":[1,2,3,4,5,6]}"#, json);
+        assert_eq!(grid, serde_json::from_str(&json).unwrap());
+
+        let result = serde_json::from_str::<Grid<u32>>(r#"{"height":2,"width":2,"grid":[1,2,3]}"#);
+        assert!(result.is_err());
+
+        let json = serde_json::to_string(&(Position::new(1, 2), Direction::UpLeft)).unwrap();
+        assert_eq!(r#"[{"y":2,"x":1},"UpLeft"]"#, json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_text() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Snapshot(#[serde(with = "serde_text")] Grid<char>);
+
+        let snapshot = Snapshot(["#..", ".#."].iter().map(|row| row.chars()).collect());
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(r##""#..\n.#.""##, json);
+        assert_eq!(snapshot, serde_json::from_str(&json).unwrap());
+
+        let empty = Snapshot(Grid::new(0, 0, vec![]).unwrap());
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(empty, serde_json::from_str(&json).unwrap());
+        for (height, width) in [(3, 0), (1, 0), (0, 2)] {
+            let grid = Grid::new(height, width, vec![]).unwrap();
+            assert!(serde_json::to_string(&Snapshot(grid)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = example_grid();
+        let rows = |grid: Grid<u32>| grid.rows().map(<[u32]>::to_vec).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![vec![4, 1], vec![5, 2], vec![6, 3]],
+            rows(grid.transform(Transform::Rotate90))
+        );
+        assert_eq!(
+            vec![vec![6, 5, 4], vec![3, 2, 1]],
+            rows(grid.transform(Transform::Rotate180))
+        );
+        assert_eq!(
+            vec![vec![3, 6], vec![2, 5], vec![1, 4]],
+            rows(grid.transform(Transform::Rotate270))
+        );
+        assert_eq!(
+            vec![vec![3, 2, 1], vec![6, 5, 4]],
+            rows(grid.transform(Transform::FlipHorizontal))
+        );
+        assert_eq!(
+            vec![vec![4, 5, 6], vec![1, 2, 3]],
+            rows(grid.transform(Transform::FlipVertical))
+        );
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            rows(grid.transform(Transform::Transpose))
+        );
+        assert_eq!(
+            vec![vec![6, 3], vec![5, 2], vec![4, 1]],
+            rows(grid.transform(Transform::AntiTranspose))
+        );
+    }
+
+    #[test]
+    fn test_crop() {
+        let mut grid = example_grid();
+        grid.crop(Position::new(1, 0), 2, 2);
+        assert_eq!(Grid::new(2, 2, vec![2, 3, 5, 6]), Some(grid));
+    }
+
+    #[test]
+    fn test_add_direction_matches_delta() {
+        let position = Position::new(5, 5);
+        for direction in DIRECTIONS {
+            assert_eq!(position + direction, position + direction.delta());
+            assert_eq!(
+                Some(position),
+                (position + direction * 3).unwrap() + direction.reverse() * 3
+            );
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let result = Position::new(2, 7) - Position::new(5, 3);
+        const EXPECTED: (isize, isize) = (-3, 4);
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[test]
+    fn test_rotate_45() {
+        for direction in DIRECTIONS {
+            assert_eq!(
+                direction.rotate_left(),
+                direction.rotate_left_45().rotate_left_45()
+            );
+            assert_eq!(
+                direction.rotate_right(),
+                direction.rotate_right_45().rotate_right_45()
+            );
+            assert_eq!(direction, direction.rotate_left_45().rotate_right_45());
+        }
+    }
+
+    #[test]
+    fn test_try_from_char() {
+        let result: Result<Vec<Direction>, char> =
+            "^v<>UDLRNSWE".chars().map(Direction::try_from).collect();
+        let expected = [CARDINAL_DIRECTIONS; 3].concat();
+        assert_eq!(Ok(expected), result);
+        assert_eq!(Err('x'), Direction::try_from('x'));
+    }
+}
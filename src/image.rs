@@ -0,0 +1,177 @@
+use crate::grid::{GridLike, Position};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    height: usize,
+    width: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Renders each cell as a `scale` x `scale` square of the colour given by `colour`. Cells
+    /// that are not occupied (in a sparse or bit-packed grid) are left black.
+    pub fn from_grid<G, F>(grid: &G, scale: usize, colour: F) -> Self
+    where
+        G: GridLike,
+        F: Fn(Position, &G::Item) -> Rgb,
+    {
+        let height = grid.height() * scale;
+        let width = grid.width() * scale;
+        let mut pixels = vec![BLACK; height * width];
+
+        for (position, cell) in grid.cells() {
+            let rgb = colour(position, cell);
+            for idy in position.y() * scale..(position.y() + 1) * scale {
+                let start = idy * width + position.x() * scale;
+                pixels[start..start + scale].fill(rgb);
+            }
+        }
+
+        Image {
+            height,
+            width,
+            pixels,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(self.pixels.as_flattened())?;
+        writer.flush()
+    }
+
+    #[cfg(feature = "png")]
+    pub fn write_png<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+        writer
+            .write_image_data(self.pixels.as_flattened())
+            .map_err(std::io::Error::other)?;
+        writer.finish().map_err(std::io::Error::other)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: ImageFormat) -> std::io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            #[cfg(feature = "png")]
+            ImageFormat::Png => self.write_png(writer),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    Ppm,
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            #[cfg(feature = "png")]
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+/// Writes numbered images (`frame_00000.ppm`, `frame_00001.ppm`, ...) to a directory, one per
+/// step of a simulation or search.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FrameWriter {
+    directory: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    frames: usize,
+}
+
+impl FrameWriter {
+    pub fn new<P: AsRef<Path>>(
+        directory: P,
+        format: ImageFormat,
+        scale: usize,
+    ) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&directory)?;
+        Ok(FrameWriter {
+            directory: directory.as_ref().to_path_buf(),
+            format,
+            scale,
+            frames: 0,
+        })
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn write_image(&mut self, image: &Image) -> std::io::Result<PathBuf> {
+        let path = self.directory.join(format!(
+            "frame_{:05}.{}",
+            self.frames,
+            self.format.extension()
+        ));
+        image.save(&path, self.format)?;
+        self.frames += 1;
+        Ok(path)
+    }
+
+    pub fn write_grid<G, F>(&mut self, grid: &G, colour: F) -> std::io::Result<PathBuf>
+    where
+        G: GridLike,
+        F: Fn(Position, &G::Item) -> Rgb,
+    {
+        self.write_image(&Image::from_grid(grid, self.scale, colour))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn test_from_grid() {
+        let grid = Grid::new(1, 2, vec![false, true]).unwrap();
+        let result = Image::from_grid(&grid, 2, |_, &b| if b { WHITE } else { BLACK });
+
+        const EXPECTED: [Rgb; 8] = [BLACK, BLACK, WHITE, WHITE, BLACK, BLACK, WHITE, WHITE];
+        assert_eq!((2, 4), (result.height(), result.width()));
+        assert_eq!(EXPECTED.as_slice(), result.pixels());
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let grid = Grid::new(1, 2, vec![false, true]).unwrap();
+        let image = Image::from_grid(&grid, 1, |_, &b| if b { WHITE } else { BLACK });
+        let mut result = Vec::new();
+        image.write_ppm(&mut result).unwrap();
+
+        const EXPECTED: &[u8] = b"P6\n2 1\n255\n\x00\x00\x00\xff\xff\xff";
+        assert_eq!(EXPECTED, result);
+    }
+}
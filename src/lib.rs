@@ -1 +1,19 @@
+pub mod bitgrid;
+pub mod cycle;
+pub mod diff;
+pub mod flow;
+pub mod graph;
 pub mod grid;
+pub mod image;
+pub mod linear;
+pub mod maze;
+pub mod number_theory;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod pattern;
+pub mod polygon;
+pub mod rational;
+pub mod search;
+pub mod sparse_grid;
+pub mod union_find;
+pub mod walker;
@@ -0,0 +1,179 @@
+use crate::rational::Rational;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSystemError {
+    /// No assignment satisfies every equation.
+    Inconsistent,
+    /// There are infinitely many solutions, parametrised by these variables.
+    Underdetermined(Vec<usize>),
+    /// An intermediate fraction did not fit in 128 bits.
+    Overflow,
+}
+
+/// Solves `coefficients · x = constants` exactly by Gauss–Jordan elimination. There may be more
+/// equations than unknowns, as long as they agree. Panics if the rows have different lengths or
+/// if there is not one constant per equation.
+pub fn solve(
+    coefficients: &[Vec<i64>],
+    constants: &[i64],
+) -> Result<Vec<Rational>, LinearSystemError> {
+    assert_eq!(
+        coefficients.len(),
+        constants.len(),
+        "one constant per equation"
+    );
+    let variables = coefficients.first().map_or(0, Vec::len);
+    assert!(
+        coefficients.iter().all(|row| row.len() == variables),
+        "ragged coefficients"
+    );
+
+    let mut rows: Vec<Vec<Rational>> = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(row, &constant)| {
+            row.iter()
+                .chain([&constant])
+                .map(|&value| Rational::from(value))
+                .collect()
+        })
+        .collect();
+
+    let mut pivots = Vec::new();
+    for column in 0..variables {
+        let rank = pivots.len();
+        let Some(pivot) = (rank..rows.len()).find(|&idx| !rows[idx][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let scale = Rational::ONE.checked_div(rows[rank][column]);
+        let scale = scale.ok_or(LinearSystemError::Overflow)?;
+        for value in rows[rank].iter_mut() {
+            *value = value
+                .checked_mul(scale)
+                .ok_or(LinearSystemError::Overflow)?;
+        }
+        let pivot_row = rows[rank].clone();
+        for (_, row) in rows.iter_mut().enumerate().filter(|&(idx, _)| idx != rank) {
+            let factor = row[column];
+            if !factor.is_zero() {
+                for (value, &pivot_value) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                    *value = factor
+                        .checked_mul(pivot_value)
+                        .and_then(|product| value.checked_sub(product))
+                        .ok_or(LinearSystemError::Overflow)?;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    // Every row below the rank has no coefficient left, so its constant must vanish too.
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[variables].is_zero())
+    {
+        return Err(LinearSystemError::Inconsistent);
+    }
+    if pivots.len() < variables {
+        let free = (0..variables)
+            .filter(|column| !pivots.contains(column))
+            .collect();
+        return Err(LinearSystemError::Underdetermined(free));
+    }
+
+    Ok(rows[..variables].iter().map(|row| row[variables]).collect())
+}
+
+/// The unique solution of the system, provided every value is a non-negative integer.
+pub fn non_negative_integer_solution(
+    coefficients: &[Vec<i64>],
+    constants: &[i64],
+) -> Option<Vec<i64>> {
+    solve(coefficients, constants)
+        .ok()?
+        .iter()
+        .map(|value| value.to_integer().filter(|&value| value >= 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Claw machines from the 2024 day 13 example: the presses of buttons A and B reaching the
+    // prize, one equation per axis.
+    #[test]
+    fn test_non_negative_integer_solution() {
+        let coefficients = vec![vec![94, 22], vec![34, 67]];
+        assert_eq!(
+            Some(vec![80, 40]),
+            non_negative_integer_solution(&coefficients, &[8400, 5400])
+        );
+
+        let coefficients = vec![vec![26, 67], vec![66, 21]];
+        assert_eq!(
+            None,
+            non_negative_integer_solution(&coefficients, &[12748, 12176])
+        );
+
+        const OFFSET: i64 = 10_000_000_000_000;
+        let solution =
+            non_negative_integer_solution(&coefficients, &[OFFSET + 12748, OFFSET + 12176]);
+        const EXPECTED: i64 = 459236326669;
+        assert_eq!(
+            Some(EXPECTED),
+            solution.map(|presses| 3 * presses[0] + presses[1])
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let coefficients = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        let expected: Vec<Rational> = [2, 3, -1].map(Rational::from).to_vec();
+        assert_eq!(Ok(expected), solve(&coefficients, &[8, -11, -3]));
+
+        let coefficients = vec![vec![1, 2], vec![2, 4]];
+        assert_eq!(
+            Err(LinearSystemError::Underdetermined(vec![1])),
+            solve(&coefficients, &[3, 6])
+        );
+        assert_eq!(
+            Err(LinearSystemError::Inconsistent),
+            solve(&coefficients, &[3, 7])
+        );
+
+        let coefficients = vec![vec![1, 1], vec![1, -1], vec![3, 1]];
+        let expected = vec![Rational::new(5, 2), Rational::new(1, 2)];
+        assert_eq!(Ok(expected), solve(&coefficients, &[3, 2, 8]));
+    }
+
+    #[test]
+    fn test_large_coefficients() {
+        let coefficients = vec![
+            vec![9876543, 1234567, 7654321],
+            vec![3456789, 8765431, 2345671],
+            vec![5678901, 4321987, 9012345],
+        ];
+        let constants = [1, 2, 3];
+        let solution = solve(&coefficients, &constants).unwrap();
+        for (row, &constant) in coefficients.iter().zip(&constants) {
+            let sum = row
+                .iter()
+                .zip(&solution)
+                .fold(Rational::ZERO, |sum, (&coefficient, &value)| {
+                    sum + Rational::from(coefficient) * value
+                });
+            assert_eq!(Rational::from(constant), sum);
+        }
+        assert_eq!(
+            None,
+            non_negative_integer_solution(&coefficients, &constants)
+        );
+
+        let coefficients = vec![vec![i64::MIN, i64::MAX], vec![i64::MAX, i64::MIN]];
+        let expected = vec![Rational::from(-1); 2];
+        assert_eq!(Ok(expected), solve(&coefficients, &[1, 1]));
+    }
+}
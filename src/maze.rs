@@ -0,0 +1,222 @@
+use crate::grid::{GridLike, Position};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    /// Every cell of the corridor, from the `from` node to the `to` node, both included.
+    pub path: Vec<Position>,
+}
+
+impl Edge {
+    pub fn length(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+/// A maze compressed down to its junctions, dead ends and designated points, linked by the
+/// corridors between them. Each corridor appears as one edge in each direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JunctionGraph {
+    nodes: Vec<Position>,
+    index: HashMap<Position, usize>,
+    edges: Vec<Edge>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl JunctionGraph {
+    pub fn from_grid<G, F>(grid: &G, passable: F, points: &[Position]) -> Self
+    where
+        G: GridLike,
+        F: Fn(&G::Item) -> bool,
+    {
+        let exits = |position: Position| {
+            grid.neighbours(position)
+                .filter(|(_, value)| passable(value))
+                .map(|(p, _)| p)
+                .collect::<Vec<Position>>()
+        };
+
+        let nodes: Vec<Position> = grid
+            .cells()
+            .filter(|&(position, value)| {
+                passable(value) && (points.contains(&position) || exits(position).len() != 2)
+            })
+            .map(|(position, _)| position)
+            .collect();
+        let index: HashMap<Position, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, &position)| (position, idx))
+            .collect();
+
+        let mut edges = Vec::new();
+        let mut outgoing = vec![Vec::new(); nodes.len()];
+        for (from, &start) in nodes.iter().enumerate() {
+            for first in exits(start) {
+                let mut path = vec![start, first];
+                // Follow the corridor, never turning back, until it reaches another node.
+                while !index.contains_key(path.last().unwrap()) {
+                    let (previous, current) = (path[path.len() - 2], path[path.len() - 1]);
+                    let next = exits(current).into_iter().find(|&p| p != previous).unwrap();
+                    path.push(next);
+                }
+
+                let to = index[path.last().unwrap()];
+                outgoing[from].push(edges.len());
+                edges.push(Edge { from, to, path });
+            }
+        }
+
+        JunctionGraph {
+            nodes,
+            index,
+            edges,
+            outgoing,
+        }
+    }
+
+    pub fn nodes(&self) -> &[Position] {
+        &self.nodes
+    }
+
+    pub fn node(&self, position: Position) -> Option<usize> {
+        self.index.get(&position).copied()
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    pub fn outgoing(&self, node: usize) -> impl Iterator<Item = &Edge> {
+        self.outgoing[node].iter().map(|&edge| &self.edges[edge])
+    }
+
+    /// Returns the length of the shortest path between two nodes, along with every cell of it.
+    pub fn shortest_path(&self, from: Position, to: Position) -> Option<(usize, Vec<Position>)> {
+        let (from, to) = (self.node(from)?, self.node(to)?);
+        let mut distances = vec![usize::MAX; self.nodes.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, from))]);
+        distances[from] = 0;
+
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if node == to {
+                break;
+            }
+            if distance > distances[node] {
+                continue;
+            }
+
+            for &edge in &self.outgoing[node] {
+                let next = self.edges[edge].to;
+                let candidate = distance + self.edges[edge].length();
+                if candidate < distances[next] {
+                    distances[next] = candidate;
+                    previous[next] = Some(edge);
+                    queue.push(Reverse((candidate, next)));
+                }
+            }
+        }
+
+        if distances[to] == usize::MAX {
+            return None;
+        }
+
+        let mut edges = Vec::new();
+        let mut node = to;
+        while let Some(edge) = previous[node] {
+            edges.push(edge);
+            node = self.edges[edge].from;
+        }
+        let mut path = vec![self.nodes[from]];
+        for &edge in edges.iter().rev() {
+            path.extend_from_slice(&self.edges[edge].path[1..]);
+        }
+        Some((distances[to], path))
+    }
+
+    /// Returns the length of the longest path between two nodes that never visits a node
+    /// twice. This explores every simple path, which the compression keeps tractable.
+    pub fn longest_path(&self, from: Position, to: Position) -> Option<usize> {
+        let (from, to) = (self.node(from)?, self.node(to)?);
+        let mut visited = vec![false; self.nodes.len()];
+        self.longest_from(from, to, &mut visited)
+    }
+
+    fn longest_from(&self, node: usize, to: usize, visited: &mut [bool]) -> Option<usize> {
+        if node == to {
+            return Some(0);
+        }
+
+        visited[node] = true;
+        let mut longest = None;
+        for edge in self.outgoing(node) {
+            if !visited[edge.to]
+                && let Some(rest) = self.longest_from(edge.to, to, visited)
+            {
+                longest = longest.max(Some(rest + edge.length()));
+            }
+        }
+        visited[node] = false;
+        longest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::search;
+
+    const INPUT: &str = "\
+        #.#####################\n\
+        #.......#########...###\n\
+        #######.#########.#.###\n\
+        ###.....#.>.>.###.#.###\n\
+        ###v#####.#v#.###.#.###\n\
+        ###.>...#.#.#.....#...#\n\
+        ###v###.#.#.#########.#\n\
+        ###...#.#.#.......#...#\n\
+        #####.#.#.#######.#.###\n\
+        #.....#.#.#.......#...#\n\
+        #.#####.#.#.#########v#\n\
+        #.#...#...#...###...>.#\n\
+        #.#.#v#######v###.###v#\n\
+        #...#.>.#...>.>.#.###.#\n\
+        #####v#.#.###v#.#.###.#\n\
+        #.....#...#...#.#.#...#\n\
+        #.#########.###.#.#.###\n\
+        #...###...#...#...#.###\n\
+        ###.###.#.###v#####v###\n\
+        #...#...#.#.>.>.#.>.###\n\
+        #.###.###.#.###.#.#v###\n\
+        #.....###...###...#...#\n\
+        #####################.#\
+    ";
+
+    #[test]
+    fn test_longest_path() {
+        let grid: Grid<char> = INPUT.lines().map(str::chars).collect();
+        let (start, end) = (Position::new(1, 0), Position::new(21, 22));
+        let graph = JunctionGraph::from_grid(&grid, |&c| c != '#', &[start, end]);
+
+        const EXPECTED: usize = 154;
+        assert_eq!(Some(EXPECTED), graph.longest_path(start, end));
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let grid: Grid<char> = INPUT.lines().map(str::chars).collect();
+        let (start, end) = (Position::new(1, 0), Position::new(21, 22));
+        let graph = JunctionGraph::from_grid(&grid, |&c| c != '#', &[start, end]);
+        let (length, path) = graph.shortest_path(start, end).unwrap();
+
+        let expected = search::distances(&grid, start, |&c| c != '#')[&end];
+        assert_eq!(expected, length);
+        assert_eq!(length + 1, path.len());
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(w[1]) == 1));
+    }
+}
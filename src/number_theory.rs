@@ -0,0 +1,176 @@
+/// The greatest common divisor, unsigned so that even `gcd(i64::MIN, 0)` fits.
+pub fn gcd(a: i64, b: i64) -> u64 {
+    unsigned_gcd(a.unsigned_abs(), b.unsigned_abs())
+}
+
+fn unsigned_gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, always non-negative, and zero if either argument is. Returns
+/// `None` if it does not fit in an `i64`.
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    let multiple = (a.unsigned_abs() / gcd(a, b)).checked_mul(b.unsigned_abs())?;
+    i64::try_from(multiple).ok()
+}
+
+pub fn gcd_all(values: &[i64]) -> u64 {
+    values
+        .iter()
+        .fold(0, |acc, &value| unsigned_gcd(acc, value.unsigned_abs()))
+}
+
+/// The least common multiple of every value, or 1 if there are none. Returns `None` if it does
+/// not fit in an `i64`.
+pub fn lcm_all(values: &[i64]) -> Option<i64> {
+    values.iter().try_fold(1, |acc, &value| lcm(acc, value))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the greatest common divisor.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a * b` modulo `modulus`, in `0..modulus`, without overflowing.
+pub fn mul_mod(a: i64, b: i64, modulus: i64) -> i64 {
+    (a as i128 * b as i128).rem_euclid(modulus as i128) as i64
+}
+
+/// `base` to the power `exponent` modulo `modulus`, in `0..modulus`.
+pub fn mod_pow(base: i64, mut exponent: u64, modulus: i64) -> i64 {
+    let mut base = base.rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The inverse of `a` modulo `modulus`, if they are coprime and the modulus is positive.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair, returning
+/// the smallest non-negative solution along with the least common multiple of the moduli, which
+/// need not be coprime. Returns `None` if a modulus is not positive, if the congruences
+/// contradict each other, or if that least common multiple does not fit in an `i64`.
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(residue, modulus), &(r, m)| {
+            if m <= 0 {
+                return None;
+            }
+            let r = r.rem_euclid(m);
+            let (g, x, _) = extended_gcd(modulus, m);
+            if (r - residue) % g != 0 {
+                return None;
+            }
+
+            // residue + modulus * k ≡ r (mod m), with k = (r - residue) / g * x (mod m / g).
+            let step = m / g;
+            let k = mul_mod((r - residue) / g, x, step);
+            let combined = modulus as i128 * step as i128;
+            let solution = (residue as i128 + modulus as i128 * k as i128).rem_euclid(combined);
+            // The solution is below the combined modulus, so it fits whenever the modulus does.
+            Some((solution as i64, i64::try_from(combined).ok()?))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(6, gcd(-12, 18));
+        assert_eq!(1 << 63, gcd(i64::MIN, 0));
+        assert_eq!(1 << 62, gcd_all(&[i64::MIN, 1 << 62]));
+        assert_eq!(Some(1 << 62), lcm(-(1 << 62), 2));
+        assert_eq!(None, lcm(i64::MIN, 2));
+        assert_eq!(Some(36), lcm(-12, 18));
+        assert_eq!(4, gcd_all(&[8, 12, 20]));
+        assert_eq!(Some(120), lcm_all(&[8, 12, 20]));
+        assert_eq!(None, lcm(i64::MAX, 2));
+        assert_eq!(None, lcm_all(&[3, 1 << 40, (1 << 40) - 1]));
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(2, g);
+        assert_eq!(g, 240 * x + 46 * y);
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        const M: i64 = 1_000_000_007;
+        assert_eq!(M - 1, mul_mod(M - 1, M + 1, M));
+        assert_eq!(1, mod_pow(2, (M - 1) as u64, M));
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(None, mod_inverse(4, 12));
+        assert_eq!(None, mod_inverse(4, 0));
+        assert_eq!(0, mod_pow(5, 0, 1));
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(
+            Some((23, 105)),
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)])
+        );
+        assert_eq!(Some((10, 12)), chinese_remainder(&[(4, 6), (2, 4)]));
+        assert_eq!(None, chinese_remainder(&[(1, 6), (2, 4)]));
+        assert_eq!(None, chinese_remainder(&[(1, 6), (2, 0)]));
+        assert_eq!(None, chinese_remainder(&[(1, -6)]));
+
+        // Large coprime moduli, whose product still fits in an i64.
+        const P: i64 = 1_000_000_007;
+        const Q: i64 = 998_244_353;
+        let (x, modulus) = chinese_remainder(&[(P - 1, P), (Q - 2, Q)]).unwrap();
+        assert_eq!(P * Q, modulus);
+        assert_eq!((P - 1, Q - 2), (x % P, x % Q));
+
+        // Primes above 2^40, whose product does not.
+        assert_eq!(
+            None,
+            chinese_remainder(&[(1, 1_099_511_627_791), (2, 1_099_511_627_817)])
+        );
+
+        // Buses departing at consecutive minutes, from the 2020 day 13 example.
+        let buses = [(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
+        let congruences: Vec<(i64, i64)> =
+            buses.iter().map(|&(id, offset)| (-offset, id)).collect();
+        const EXPECTED: i64 = 1068781;
+        assert_eq!(
+            Some(EXPECTED),
+            chinese_remainder(&congruences).map(|(x, _)| x)
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs both variants one after the other (so they do not compete for the thread pool),
+/// reports their timings, and checks that they agree before returning the result.
+pub fn compare_timings<T: PartialEq + Debug>(
+    label: &str,
+    sequential: impl FnOnce() -> T,
+    parallel: impl FnOnce() -> T,
+) -> T {
+    let (expected, sequential) = timed(sequential);
+    let (result, parallel) = timed(parallel);
+    assert_eq!(
+        expected, result,
+        "{}: sequential and parallel results differ",
+        label
+    );
+
+    println!(
+        "{}: sequential {:?}, parallel {:?} ({:.2}x)",
+        label,
+        sequential,
+        parallel,
+        sequential.as_secs_f64() / parallel.as_secs_f64()
+    );
+
+    result
+}
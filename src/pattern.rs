@@ -0,0 +1,124 @@
+use crate::grid::{DIRECTIONS, Direction, Grid, Position, TRANSFORMS, Transform};
+
+/// Finds every start position and direction along which the grid reads `sequence`. A
+/// palindrome is found twice, once in each direction.
+pub fn find_sequence<T: PartialEq>(grid: &Grid<T>, sequence: &[T]) -> Vec<(Position, Direction)> {
+    let Some(first) = sequence.first() else {
+        return Vec::new();
+    };
+
+    grid.iter()
+        .filter(|(_, value)| *value == first)
+        .flat_map(|(position, _)| DIRECTIONS.map(|direction| (position, direction)))
+        .filter(|&(position, direction)| {
+            let mut cells = grid.get_iter(position.line(direction));
+            sequence
+                .iter()
+                .all(|expected| cells.next().is_some_and(|value| value == expected))
+        })
+        .collect()
+}
+
+/// Finds the top left corner of every placement of `pattern` in the grid, where `None` cells
+/// of the pattern match anything. A pattern without cells matches nowhere.
+pub fn find_subgrid<T: PartialEq>(grid: &Grid<T>, pattern: &Grid<Option<T>>) -> Vec<Position> {
+    if pattern.height() == 0
+        || pattern.width() == 0
+        || pattern.height() > grid.height()
+        || pattern.width() > grid.width()
+    {
+        return Vec::new();
+    }
+
+    (0..=grid.height() - pattern.height())
+        .flat_map(|idy| {
+            (0..=grid.width() - pattern.width()).map(move |idx| Position::new(idx, idy))
+        })
+        .filter(|&corner| {
+            pattern.iter().all(|(offset, expected)| {
+                expected.as_ref().is_none_or(|expected| {
+                    let position = Position::new(corner.x() + offset.x(), corner.y() + offset.y());
+                    grid[position] == *expected
+                })
+            })
+        })
+        .collect()
+}
+
+/// Like `find_subgrid`, but also tries every rotation and reflection of the pattern, reporting
+/// which one matched. Orientations that leave the pattern unchanged are only tried once.
+pub fn find_subgrid_any_orientation<T: Clone + PartialEq>(
+    grid: &Grid<T>,
+    pattern: &Grid<Option<T>>,
+) -> Vec<(Position, Transform)> {
+    let mut orientations: Vec<(Transform, Grid<Option<T>>)> = Vec::new();
+    for transform in TRANSFORMS {
+        let oriented = pattern.transform(transform);
+        if !orientations.iter().any(|(_, other)| *other == oriented) {
+            orientations.push((transform, oriented));
+        }
+    }
+
+    orientations
+        .iter()
+        .flat_map(|(transform, oriented)| {
+            find_subgrid(grid, oriented)
+                .into_iter()
+                .map(move |position| (position, *transform))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+        MMMSXXMASM\n\
+        MSAMXMSMSA\n\
+        AMXSXMAAMM\n\
+        MSAMASMSMX\n\
+        XMASAMXAMM\n\
+        XXAMMXXAMA\n\
+        SMSMSASXSS\n\
+        SAXAMASAAA\n\
+        MAMMMXMMMM\n\
+        MXMXAXMASX\
+    ";
+
+    fn parse(input: &str) -> Grid<char> {
+        input.lines().map(str::chars).collect()
+    }
+
+    #[test]
+    fn test_find_sequence() {
+        let grid = parse(INPUT);
+        let result = find_sequence(&grid, &['X', 'M', 'A', 'S']);
+
+        const EXPECTED: usize = 18;
+        assert_eq!(EXPECTED, result.len());
+    }
+
+    #[test]
+    fn test_find_subgrid_any_orientation() {
+        let grid = parse(INPUT);
+        let pattern: Grid<Option<char>> = ["M.S", ".A.", "M.S"]
+            .iter()
+            .map(|row| row.chars().map(|c| (c != '.').then_some(c)))
+            .collect();
+        let result = find_subgrid_any_orientation(&grid, &pattern);
+
+        const EXPECTED: usize = 9;
+        assert_eq!(EXPECTED, result.len());
+    }
+
+    #[test]
+    fn test_find_subgrid_empty_pattern() {
+        let grid = parse(INPUT);
+        for (height, width) in [(0, 0), (0, 2), (2, 0)] {
+            let pattern: Grid<Option<char>> = Grid::new(height, width, vec![]).unwrap();
+            assert!(find_subgrid(&grid, &pattern).is_empty());
+            assert!(find_subgrid_any_orientation(&grid, &pattern).is_empty());
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::grid::{Direction, Position};
+use crate::number_theory::gcd;
+
+/// A closed polygon on the integer lattice, whose last vertex connects back to the first one.
+/// Coordinates are signed and unbounded by any grid, and areas are computed on 128 bits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    vertices: Vec<(i64, i64)>,
+}
+
+impl Polygon {
+    /// Vertices are `(x, y)` pairs. Consecutive duplicates and collinear points are harmless.
+    pub fn new(vertices: Vec<(i64, i64)>) -> Self {
+        Polygon { vertices }
+    }
+
+    pub fn from_positions(positions: &[Position]) -> Self {
+        Polygon::new(
+            positions
+                .iter()
+                .map(|position| (position.x() as i64, position.y() as i64))
+                .collect(),
+        )
+    }
+
+    /// Follows the steps from the origin, as in a dig plan. The path is expected to end where
+    /// it started.
+    pub fn from_steps<I: IntoIterator<Item = (Direction, i64)>>(steps: I) -> Self {
+        let mut current = (0, 0);
+        let mut vertices = vec![current];
+        for (direction, length) in steps {
+            let (dx, dy) = direction.delta();
+            current = (
+                current.0 + dx as i64 * length,
+                current.1 + dy as i64 * length,
+            );
+            vertices.push(current);
+        }
+        Polygon::new(vertices)
+    }
+
+    pub fn vertices(&self) -> &[(i64, i64)] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// Twice the enclosed area, by the shoelace formula, so that it stays an integer.
+    pub fn double_area(&self) -> i128 {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| x1 as i128 * y2 as i128 - x2 as i128 * y1 as i128)
+            .sum::<i128>()
+            .abs()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.double_area() as f64 / 2.0
+    }
+
+    /// The number of lattice points on the edges, which is also the length of the boundary
+    /// when every edge is horizontal or vertical.
+    pub fn boundary_points(&self) -> i128 {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| gcd(x2 - x1, y2 - y1) as i128)
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem. The polygon is assumed
+    /// to be simple; one without area, such as fewer than three distinct vertices, has none.
+    pub fn interior_points(&self) -> i128 {
+        let double_area = self.double_area();
+        if double_area == 0 {
+            return 0;
+        }
+        (double_area - self.boundary_points()) / 2 + 1
+    }
+
+    /// The number of lattice points inside or on the boundary, such as the cells of a dig plan
+    /// with its trench.
+    pub fn lattice_points(&self) -> i128 {
+        self.interior_points() + self.boundary_points()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+        R 6 (#70c710)\n\
+        D 5 (#0dc571)\n\
+        L 2 (#5713f0)\n\
+        D 2 (#d2c081)\n\
+        R 2 (#59c680)\n\
+        D 2 (#411b91)\n\
+        L 5 (#8ceca2)\n\
+        U 2 (#caa173)\n\
+        L 1 (#1b58a2)\n\
+        U 2 (#caa171)\n\
+        R 2 (#7807d2)\n\
+        U 3 (#a77fa3)\n\
+        L 2 (#015232)\n\
+        U 2 (#7a21e3)\
+    ";
+
+    #[test]
+    fn test_dig_plan() {
+        let steps = INPUT.lines().map(|line| {
+            let mut parts = line.split_whitespace();
+            let direction = Direction::try_from(parts.next().unwrap().chars().next().unwrap());
+            (direction.unwrap(), parts.next().unwrap().parse().unwrap())
+        });
+
+        const EXPECTED: i128 = 62;
+        assert_eq!(EXPECTED, Polygon::from_steps(steps).lattice_points());
+    }
+
+    #[test]
+    fn test_huge_coordinates() {
+        // Coordinates whose products no longer fit in 64 bits.
+        const SIDE: i64 = 10_000_000_000;
+        let steps = [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ]
+        .map(|direction| (direction, SIDE));
+
+        const EXPECTED: i128 = (SIDE as i128 + 1) * (SIDE as i128 + 1);
+        assert_eq!(EXPECTED, Polygon::from_steps(steps).lattice_points());
+    }
+
+    #[test]
+    fn test_positions() {
+        let corners = [(0, 0), (2, 0), (2, 2), (0, 2)].map(|(x, y)| Position::new(x, y));
+        let polygon = Polygon::from_positions(&corners);
+
+        assert_eq!(4.0, polygon.area());
+        assert_eq!(8, polygon.boundary_points());
+        assert_eq!(1, polygon.interior_points());
+
+        let triangle = Polygon::new(vec![(0, 0), (4, 0), (0, 4)]);
+        assert_eq!(
+            (12, 3),
+            (triangle.boundary_points(), triangle.interior_points())
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        for vertices in [
+            vec![],
+            vec![(3, 4)],
+            vec![(0, 0), (4, 0)],
+            vec![(0, 0), (2, 2), (4, 4)],
+        ] {
+            let polygon = Polygon::new(vertices);
+            assert_eq!((0, 0), (polygon.double_area(), polygon.interior_points()));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator. Both parts are
+/// stored on 128 bits, so that sums and products of `i64` fractions stay exact. The `checked_*`
+/// methods return `None` when a result does not fit, where the operators panic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    // Only 2^127 does not fit, and as `i128::MIN` it still divides both arguments exactly.
+    i128::try_from(a.max(1)).unwrap_or(i128::MIN)
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Rational = Rational {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// Panics if `denominator` is zero, or if either part is `i128::MIN` once reduced.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        Self::reduced(numerator, denominator).expect("rational overflow")
+    }
+
+    fn reduced(numerator: i128, denominator: i128) -> Option<Self> {
+        assert!(denominator != 0, "zero denominator");
+        let divisor = gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if denominator < 0 {
+            Some(Rational {
+                numerator: numerator.checked_neg()?,
+                denominator: denominator.checked_neg()?,
+            })
+        } else {
+            Some(Rational {
+                numerator,
+                denominator,
+            })
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// The value as an `i64`, if it is an integer in range.
+    pub fn to_integer(&self) -> Option<i64> {
+        if self.is_integer() {
+            i64::try_from(self.numerator).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        Self::reduced(self.denominator, self.numerator).expect("rational overflow")
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        // Scaling by the lcm of the denominators rather than their product keeps the
+        // intermediates small.
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let (left, right) = (self.denominator / divisor, rhs.denominator / divisor);
+        Self::reduced(
+            self.numerator
+                .checked_mul(right)?
+                .checked_add(rhs.numerator.checked_mul(left)?)?,
+            self.denominator.checked_mul(right)?,
+        )
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // Cancelling across before multiplying keeps the intermediates small.
+        let left = gcd(self.numerator, rhs.denominator);
+        let right = gcd(rhs.numerator, self.denominator);
+        Self::reduced(
+            (self.numerator / left).checked_mul(rhs.numerator / right)?,
+            (self.denominator / right).checked_mul(rhs.denominator / left)?,
+        )
+    }
+
+    /// Returns `None` if `rhs` is zero, like the integer `checked_div`.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+        self.checked_mul(Self::reduced(rhs.denominator, rhs.numerator)?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational {
+            numerator: value as i128,
+            denominator: 1,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("rational overflow")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("rational overflow")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("rational overflow")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.is_zero(), "division by zero");
+        self.checked_div(rhs).expect("rational overflow")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("rational overflow")
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare the continued fraction expansions term by term, which cannot overflow where
+        // cross-multiplying could.
+        let (mut a, mut b) = (self.numerator, self.denominator);
+        let (mut c, mut d) = (other.numerator, other.denominator);
+        loop {
+            let ordering = a.div_euclid(b).cmp(&c.div_euclid(d));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            let (left, right) = (a.rem_euclid(b), c.rem_euclid(d));
+            match (left, right) {
+                (0, 0) => return Ordering::Equal,
+                (0, _) => return Ordering::Less,
+                (_, 0) => return Ordering::Greater,
+                // left / b < right / d exactly when d / right < b / left.
+                _ => (a, b, c, d) = (d, right, b, left),
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let (a, b) = (Rational::new(1, 6), Rational::new(-3, -4));
+
+        assert_eq!(Rational::new(11, 12), a + b);
+        assert_eq!(Rational::new(-7, 12), a - b);
+        assert_eq!(Rational::new(1, 8), a * b);
+        assert_eq!(Rational::new(2, 9), a / b);
+        assert_eq!(Rational::new(4, 3), b.recip());
+        assert!(a < b && -b < a);
+        assert_eq!(Some(3), (Rational::new(9, 4) + b).to_integer());
+
+        let min = Rational::from(i64::MIN);
+        assert_eq!(Some(i64::MAX), (-min - Rational::ONE).to_integer());
+        assert_eq!(None, (-min).to_integer());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let huge = Rational::new(i128::MAX, 1);
+        assert_eq!(None, huge.checked_add(Rational::ONE));
+        assert_eq!(None, huge.checked_mul(Rational::from(2)));
+        assert_eq!(None, Rational::ONE.checked_div(Rational::ZERO));
+        assert_eq!(Some(Rational::ONE), huge.checked_div(huge));
+
+        // Ordering would overflow if it cross-multiplied.
+        let (a, b) = (Rational::new(i128::MAX, 3), Rational::new(i128::MAX - 1, 3));
+        assert!(b < a && -a < -b);
+        assert!(Rational::new(1, i128::MAX) < Rational::new(1, i128::MAX - 1));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("-2/3", Rational::new(4, -6).to_string());
+        assert_eq!("5", Rational::from(5).to_string());
+        assert_eq!(Rational::ZERO, Rational::new(0, -7));
+    }
+}
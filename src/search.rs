@@ -0,0 +1,154 @@
+use crate::grid::{GridLike, Position};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Breadth-first search over the orthogonal neighbours, returning the distance from `start` to
+/// every reachable cell for which `passable` holds.
+pub fn distances<G, F>(grid: &G, start: Position, passable: F) -> HashMap<Position, usize>
+where
+    G: GridLike,
+    F: Fn(&G::Item) -> bool,
+{
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(position) = queue.pop_front() {
+        let distance = distances[&position];
+        for (neighbour, value) in grid.neighbours(position) {
+            if passable(value) && !distances.contains_key(&neighbour) {
+                distances.insert(neighbour, distance + 1);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Returns one of the shortest paths from `start` to `goal`, both included.
+pub fn shortest_path<G, F>(
+    grid: &G,
+    start: Position,
+    goal: Position,
+    passable: F,
+) -> Option<Vec<Position>>
+where
+    G: GridLike,
+    F: Fn(&G::Item) -> bool,
+{
+    let mut previous = HashMap::from([(start, start)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(position) = queue.pop_front() {
+        if position == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while current != start {
+                current = previous[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for (neighbour, value) in grid.neighbours(position) {
+            if passable(value) && !previous.contains_key(&neighbour) {
+                previous.insert(neighbour, position);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the orthogonally connected region of cells matching `predicate` around `start`,
+/// which is empty if `start` itself does not match.
+pub fn flood_fill<G, F>(grid: &G, start: Position, predicate: F) -> HashSet<Position>
+where
+    G: GridLike,
+    F: Fn(&G::Item) -> bool,
+{
+    if !grid.get(start).is_some_and(&predicate) {
+        return HashSet::new();
+    }
+
+    let mut region = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(position) = stack.pop() {
+        for (neighbour, value) in grid.neighbours(position) {
+            if predicate(value) && region.insert(neighbour) {
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    region
+}
+
+/// Splits the cells matching `predicate` into orthogonally connected regions.
+pub fn regions<G, F>(grid: &G, predicate: F) -> Vec<HashSet<Position>>
+where
+    G: GridLike,
+    F: Fn(&G::Item) -> bool,
+{
+    let mut seen = HashSet::new();
+    let mut regions = Vec::new();
+
+    for (position, value) in grid.cells() {
+        if predicate(value) && !seen.contains(&position) {
+            let region = flood_fill(grid, position, &predicate);
+            seen.extend(region.iter().copied());
+            regions.push(region);
+        }
+    }
+
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::sparse_grid::SparseGrid;
+
+    const MAZE: &str = "\
+        S.#.\n\
+        .##.\n\
+        ...E\
+    ";
+
+    fn parse(input: &str) -> Grid<char> {
+        let height = input.lines().count();
+        let width = input.lines().next().unwrap().len();
+        Grid::new(height, width, input.lines().flat_map(str::chars).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_shortest_path_dense_and_sparse() {
+        let grid = parse(MAZE);
+        let sparse = SparseGrid::from_grid(&grid, |&c| c != '#');
+        let (start, goal) = (Position::new(0, 0), Position::new(3, 2));
+
+        const EXPECTED: usize = 6;
+        let dense = shortest_path(&grid, start, goal, |&c| c != '#').unwrap();
+        assert_eq!(EXPECTED, dense.len());
+        let sparse = shortest_path(&sparse, start, goal, |_| true).unwrap();
+        assert_eq!(dense, sparse);
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = parse(MAZE);
+        let mut result: Vec<usize> = regions(&grid, |&c| c == '#')
+            .iter()
+            .map(HashSet::len)
+            .collect();
+        result.sort_unstable();
+
+        assert_eq!(vec![3], result);
+        assert_eq!(
+            9,
+            flood_fill(&grid, Position::new(0, 0), |&c| c != '#').len()
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::grid::{Grid, GridLike, GridLikeMut, Position};
+use std::collections::HashMap;
+
+// The bounds grow to cover every inserted position, so that a sparse grid can be built without
+// knowing its extent beforehand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    height: usize,
+    width: usize,
+    cells: HashMap<Position, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(height: usize, width: usize) -> Self {
+        SparseGrid {
+            height,
+            width,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn from_grid<F: Fn(&T) -> bool>(grid: &Grid<T>, predicate: F) -> Self
+    where
+        T: Clone,
+    {
+        let mut sparse = SparseGrid::new(grid.height(), grid.width());
+        sparse.cells.extend(
+            grid.iter()
+                .filter(|(_, value)| predicate(value))
+                .map(|(position, value)| (position, value.clone())),
+        );
+        sparse
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        self.height = self.height.max(position.y() + 1);
+        self.width = self.width.max(position.x() + 1);
+        self.cells.insert(position, value)
+    }
+
+    pub fn remove(&mut self, position: Position) -> Option<T> {
+        self.cells.remove(&position)
+    }
+}
+
+impl<T> GridLike for SparseGrid<T> {
+    type Item = T;
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn get(&self, position: Position) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells
+            .iter()
+            .map(|(&position, value)| (position, value))
+    }
+}
+
+impl<T> GridLikeMut for SparseGrid<T> {
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut sparse = SparseGrid::new(0, 0);
+        for (position, value) in iter {
+            sparse.insert(position, value);
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_grows_bounds() {
+        let mut grid = SparseGrid::new(2, 2);
+        assert_eq!(None, grid.insert(Position::new(5, 1), 'a'));
+        assert_eq!((2, 6), (grid.height(), grid.width()));
+
+        assert_eq!(None, grid.insert(Position::new(0, 9), 'b'));
+        assert_eq!(Some('a'), grid.insert(Position::new(5, 1), 'c'));
+        assert_eq!((10, 6), (grid.height(), grid.width()));
+        assert_eq!(2, grid.len());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut grid: SparseGrid<u32> = [(Position::new(3, 4), 7)].into_iter().collect();
+        assert_eq!(Some(7), grid.remove(Position::new(3, 4)));
+        assert_eq!(None, grid.remove(Position::new(3, 4)));
+        assert!(grid.is_empty());
+        // The bounds do not shrink back.
+        assert_eq!((5, 4), (grid.height(), grid.width()));
+    }
+
+    #[test]
+    fn test_unstored_positions() {
+        let grid: SparseGrid<u32> = [(Position::new(0, 0), 1), (Position::new(2, 1), 2)]
+            .into_iter()
+            .collect();
+        let mut cells: Vec<(Position, u32)> = grid.cells().map(|(p, &v)| (p, v)).collect();
+        cells.sort();
+
+        assert_eq!(
+            vec![(Position::new(0, 0), 1), (Position::new(2, 1), 2)],
+            cells
+        );
+        assert!(grid.contains(Position::new(1, 0)));
+        assert_eq!(None, grid.get(Position::new(1, 0)));
+        assert!(!grid.contains(Position::new(3, 0)));
+        assert!(!grid.contains(Position::new(0, 2)));
+    }
+}
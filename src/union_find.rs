@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Disjoint sets over the indices `0..len`, with path compression and union by size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning `false` if they were already the same.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set containing `element`.
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    /// The number of disjoint sets.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The size of every set, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&element| self.parent[element] == element)
+            .map(|root| self.size[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/// Every pair of distinct nodes, as indices into `nodes`, sorted by increasing weight. Ties keep
+/// the order of the nodes.
+pub fn pairs_by_weight<N, W, F>(nodes: &[N], weight: F) -> Vec<(usize, usize, W)>
+where
+    W: Ord,
+    F: Fn(&N, &N) -> W,
+{
+    let mut pairs: Vec<(usize, usize, W)> = (0..nodes.len())
+        .flat_map(|a| (a + 1..nodes.len()).map(move |b| (a, b)))
+        .map(|(a, b)| (a, b, weight(&nodes[a], &nodes[b])))
+        .collect();
+    pairs.sort_by(|a, b| a.2.cmp(&b.2));
+    pairs
+}
+
+/// Kruskal's algorithm. Returns the edges of a minimum spanning forest in the order they were
+/// picked, so the last one is the edge that finally connected the graph.
+pub fn kruskal<N, W, I>(edges: I) -> Vec<(N, N, W)>
+where
+    N: Clone + Eq + Hash,
+    W: Ord,
+    I: IntoIterator<Item = (N, N, W)>,
+{
+    let mut edges: Vec<(N, N, W)> = edges.into_iter().collect();
+    edges.sort_by(|a, b| a.2.cmp(&b.2));
+
+    let mut index: HashMap<N, usize> = HashMap::new();
+    for (a, b, _) in &edges {
+        for node in [a, b] {
+            let next = index.len();
+            index.entry(node.clone()).or_insert(next);
+        }
+    }
+
+    let mut sets = UnionFind::new(index.len());
+    edges
+        .into_iter()
+        .filter(|(a, b, _)| sets.union(index[a], index[b]))
+        .collect()
+}
+
+/// Prim's algorithm over the complete graph on `nodes`, in O(n²) time without listing the
+/// edges, which suits dense point clouds better than `kruskal`. Each edge is given as a pair of
+/// indices into `nodes`, in the order they were picked.
+pub fn prim<N, W, F>(nodes: &[N], weight: F) -> Vec<(usize, usize, W)>
+where
+    W: Ord + Copy,
+    F: Fn(&N, &N) -> W,
+{
+    if nodes.is_empty() {
+        return Vec::new();
+    }
+
+    // The cheapest known edge from the tree to each node outside of it.
+    let mut best: Vec<Option<(usize, W)>> = (0..nodes.len())
+        .map(|node| Some((0, weight(&nodes[0], &nodes[node]))))
+        .collect();
+    best[0] = None;
+    let mut in_tree = vec![false; nodes.len()];
+    in_tree[0] = true;
+
+    let mut edges = Vec::with_capacity(nodes.len() - 1);
+    while let Some((node, (from, w))) = best
+        .iter()
+        .enumerate()
+        .filter_map(|(node, edge)| edge.map(|edge| (node, edge)))
+        .min_by_key(|&(_, (_, w))| w)
+    {
+        edges.push((from, node, w));
+        in_tree[node] = true;
+        best[node] = None;
+        for other in (0..nodes.len()).filter(|&other| !in_tree[other]) {
+            let candidate = weight(&nodes[node], &nodes[other]);
+            if best[other].is_none_or(|(_, current)| candidate < current) {
+                best[other] = Some((node, candidate));
+            }
+        }
+    }
+
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Position;
+
+    const INPUT: &str = "\
+        162,817,812\n\
+        57,618,57\n\
+        906,360,560\n\
+        592,479,940\n\
+        352,342,300\n\
+        466,668,158\n\
+        542,29,236\n\
+        431,825,988\n\
+        739,650,466\n\
+        52,470,668\n\
+        216,146,977\n\
+        819,987,18\n\
+        117,168,530\n\
+        805,96,715\n\
+        346,949,466\n\
+        970,615,88\n\
+        941,993,340\n\
+        862,61,35\n\
+        984,92,344\n\
+        425,690,689\
+    ";
+
+    fn parse(input: &str) -> Vec<[i64; 3]> {
+        input
+            .lines()
+            .map(|line| {
+                let mut coordinates = line.split(',').map(|c| c.parse().unwrap());
+                [(); 3].map(|_| coordinates.next().unwrap())
+            })
+            .collect()
+    }
+
+    fn squared_distance(a: &[i64; 3], b: &[i64; 3]) -> i64 {
+        a.iter().zip(b).map(|(a, b)| (a - b).pow(2)).sum()
+    }
+
+    #[test]
+    fn test_union_find() {
+        let points = parse(INPUT);
+        let mut sets = UnionFind::new(points.len());
+        for (a, b, _) in pairs_by_weight(&points, squared_distance)
+            .into_iter()
+            .take(10)
+        {
+            sets.union(a, b);
+        }
+
+        let result: usize = sets.component_sizes()[..3].iter().product();
+        const EXPECTED: usize = 40;
+        assert_eq!(11, sets.components());
+        assert_eq!(EXPECTED, result);
+    }
+
+    #[test]
+    fn test_kruskal_and_prim() {
+        let points = parse(INPUT);
+        let edges = pairs_by_weight(&points, squared_distance)
+            .into_iter()
+            .map(|(a, b, w)| (points[a], points[b], w));
+        let tree = kruskal(edges);
+        let (a, b, _) = tree.last().unwrap();
+
+        const EXPECTED: i64 = 25272;
+        assert_eq!(points.len() - 1, tree.len());
+        assert_eq!(EXPECTED, a[0] * b[0]);
+
+        let total: i64 = tree.iter().map(|edge| edge.2).sum();
+        let prim_total: i64 = prim(&points, squared_distance)
+            .iter()
+            .map(|edge| edge.2)
+            .sum();
+        assert_eq!(total, prim_total);
+    }
+
+    #[test]
+    fn test_kruskal_positions() {
+        let positions = [(0, 0), (3, 0), (0, 1), (3, 3)].map(|(x, y)| Position::new(x, y));
+        let edges = pairs_by_weight(&positions, |a, b| a.manhattan_distance(*b))
+            .into_iter()
+            .map(|(a, b, w)| (positions[a], positions[b], w));
+
+        let result: usize = kruskal(edges).iter().map(|edge| edge.2).sum();
+        const EXPECTED: usize = 7;
+        assert_eq!(EXPECTED, result);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::grid::{Direction, GridLike, Position};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct State {
+    pub position: Position,
+    pub direction: Direction,
+}
+
+impl State {
+    pub fn new(position: Position, direction: Direction) -> Self {
+        State {
+            position,
+            direction,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Step once in the current direction.
+    Forward,
+    /// Face a new direction without moving.
+    Turn(Direction),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The walker stepped out of the grid.
+    Exited,
+    /// The walker came back to a state it had already been in.
+    Loop(State),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walk {
+    /// Every state in order, starting with the initial one.
+    pub states: Vec<State>,
+    pub outcome: Outcome,
+}
+
+impl Walk {
+    pub fn visited_positions(&self) -> HashSet<Position> {
+        self.states.iter().map(|state| state.position).collect()
+    }
+}
+
+/// Moves an agent across a grid, asking `rule` what to do from each state.
+pub struct Walker<'a, G, F> {
+    grid: &'a G,
+    state: State,
+    rule: F,
+}
+
+impl<'a, G, F> Walker<'a, G, F>
+where
+    G: GridLike,
+    F: FnMut(&G, State) -> Action,
+{
+    pub fn new(grid: &'a G, start: State, rule: F) -> Self {
+        Walker {
+            grid,
+            state: start,
+            rule,
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Applies the rule once, returning the new state or `None` if the walker left the grid.
+    pub fn step(&mut self) -> Option<State> {
+        match (self.rule)(self.grid, self.state) {
+            Action::Forward => {
+                let next = (self.state.position + self.state.direction)
+                    .filter(|&position| self.grid.contains(position))?;
+                self.state.position = next;
+            }
+            Action::Turn(direction) => self.state.direction = direction,
+        }
+        Some(self.state)
+    }
+
+    /// Walks until leaving the grid or entering a loop.
+    pub fn walk(mut self) -> Walk {
+        let mut seen = HashSet::from([self.state]);
+        let mut states = vec![self.state];
+
+        while let Some(state) = self.step() {
+            if !seen.insert(state) {
+                return Walk {
+                    states,
+                    outcome: Outcome::Loop(state),
+                };
+            }
+            states.push(state);
+        }
+
+        Walk {
+            states,
+            outcome: Outcome::Exited,
+        }
+    }
+}
+
+/// The usual guard rule: turn right in front of a blocked cell, otherwise keep going.
+pub fn turn_right_when_blocked<G, B>(blocked: B) -> impl Fn(&G, State) -> Action
+where
+    G: GridLike,
+    B: Fn(&G::Item) -> bool,
+{
+    move |grid, state| {
+        let ahead = (state.position + state.direction).and_then(|p| grid.get(p));
+        if ahead.is_some_and(&blocked) {
+            Action::Turn(state.direction.rotate_right())
+        } else {
+            Action::Forward
+        }
+    }
+}
+
+// Obstacles sorted along each row and column, so that a walker following
+// `turn_right_when_blocked` can jump straight to the next obstacle in its way.
+struct Obstacles {
+    rows: Vec<Vec<usize>>,
+    columns: Vec<Vec<usize>>,
+}
+
+impl Obstacles {
+    fn new<G: GridLike, B: Fn(&G::Item) -> bool>(grid: &G, blocked: B) -> Self {
+        let mut rows = vec![Vec::new(); grid.height()];
+        let mut columns = vec![Vec::new(); grid.width()];
+        for (position, value) in grid.cells() {
+            if blocked(value) {
+                rows[position.y()].push(position.x());
+                columns[position.x()].push(position.y());
+            }
+        }
+        rows.iter_mut().for_each(|row| row.sort_unstable());
+        columns.iter_mut().for_each(|column| column.sort_unstable());
+        Obstacles { rows, columns }
+    }
+
+    // The state after walking up to the next obstacle (including `extra`) and turning right, or
+    // `None` if there is no obstacle before the edge of the grid.
+    fn jump(&self, state: State, extra: Position) -> Option<State> {
+        let (x, y) = (state.position.x(), state.position.y());
+        let position = match state.direction {
+            Direction::Up => {
+                let column = &self.columns[x];
+                let found = column[..column.partition_point(|&oy| oy < y)]
+                    .last()
+                    .copied();
+                let extra = (extra.x() == x && extra.y() < y).then_some(extra.y());
+                Position::new(x, found.max(extra)? + 1)
+            }
+            Direction::Down => {
+                let column = &self.columns[x];
+                let found = column.get(column.partition_point(|&oy| oy <= y)).copied();
+                let extra = (extra.x() == x && extra.y() > y).then_some(extra.y());
+                Position::new(x, min_option(found, extra)? - 1)
+            }
+            Direction::Left => {
+                let row = &self.rows[y];
+                let found = row[..row.partition_point(|&ox| ox < x)].last().copied();
+                let extra = (extra.y() == y && extra.x() < x).then_some(extra.x());
+                Position::new(found.max(extra)? + 1, y)
+            }
+            Direction::Right => {
+                let row = &self.rows[y];
+                let found = row.get(row.partition_point(|&ox| ox <= x)).copied();
+                let extra = (extra.y() == y && extra.x() > x).then_some(extra.x());
+                Position::new(min_option(found, extra)? - 1, y)
+            }
+            _ => panic!("only cardinal directions are supported"),
+        };
+        Some(State::new(position, state.direction.rotate_right()))
+    }
+}
+
+fn min_option(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Finds every position where adding a single obstacle would trap a walker following
+/// `turn_right_when_blocked` in a loop. Only positions on the original path matter, and each
+/// candidate is checked by jumping from obstacle to obstacle rather than cell by cell. Returns
+/// `None` if the walker starts facing a diagonal direction, which the jumps do not support.
+pub fn loop_obstructions<G, B>(grid: &G, start: State, blocked: B) -> Option<HashSet<Position>>
+where
+    G: GridLike,
+    B: Fn(&G::Item) -> bool,
+{
+    if start.direction.is_diagonal() {
+        return None;
+    }
+
+    let walk = Walker::new(grid, start, turn_right_when_blocked(&blocked)).walk();
+    let obstacles = Obstacles::new(grid, &blocked);
+
+    let obstructions = walk
+        .visited_positions()
+        .into_iter()
+        .filter(|&candidate| candidate != start.position)
+        .filter(|&candidate| {
+            let mut seen = HashSet::new();
+            let mut state = start;
+            while let Some(next) = obstacles.jump(state, candidate) {
+                if !seen.insert(next) {
+                    return true;
+                }
+                state = next;
+            }
+            false
+        })
+        .collect();
+    Some(obstructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const INPUT: &str = "\
+        ....#.....\n\
+        .........#\n\
+        ..........\n\
+        ..#.......\n\
+        .......#..\n\
+        ..........\n\
+        .#..^.....\n\
+        ........#.\n\
+        #.........\n\
+        ......#...\
+    ";
+
+    fn parse(input: &str) -> (Grid<char>, State) {
+        let grid: Grid<char> = input.lines().map(str::chars).collect();
+        let start = grid.find(|&c| c == '^').unwrap();
+        (grid, State::new(start, Direction::Up))
+    }
+
+    #[test]
+    fn test_walk() {
+        let (grid, start) = parse(INPUT);
+        let walk = Walker::new(&grid, start, turn_right_when_blocked(|&c| c == '#')).walk();
+
+        const EXPECTED: usize = 41;
+        assert_eq!(Outcome::Exited, walk.outcome);
+        assert_eq!(EXPECTED, walk.visited_positions().len());
+    }
+
+    #[test]
+    fn test_walk_loop() {
+        let (mut grid, start) = parse(INPUT);
+        grid[Position::new(3, 6)] = '#';
+        let walk = Walker::new(&grid, start, turn_right_when_blocked(|&c| c == '#')).walk();
+
+        assert!(matches!(walk.outcome, Outcome::Loop(_)));
+    }
+
+    #[test]
+    fn test_loop_obstructions() {
+        let (grid, start) = parse(INPUT);
+        let result = loop_obstructions(&grid, start, |&c| c == '#').unwrap();
+
+        const EXPECTED: usize = 6;
+        assert_eq!(EXPECTED, result.len());
+        assert!(result.contains(&Position::new(3, 6)));
+    }
+
+    #[test]
+    fn test_loop_obstructions_diagonal_start() {
+        let (grid, start) = parse(INPUT);
+        let start = State::new(start.position, Direction::UpRight);
+
+        assert_eq!(None, loop_obstructions(&grid, start, |&c| c == '#'));
+    }
+}